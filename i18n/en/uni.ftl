@@ -21,3 +21,279 @@ btn-confirm = Confirm
 tab-coin-balance = Coin Balance
 tab-coin-prices = Coin Prices
 no-tab-activate-warning = No tab activated, this should not occur
+tab-dca-plans = DCA Plans
+dca-symbol = Coin
+dca-amount = Amount per buy
+dca-currency = Currency
+dca-start-date = Start date
+dca-add-plan = Add plan
+dca-no-plans = No DCA plans yet
+dca-frequency-daily = Daily
+dca-frequency-weekly = Weekly
+dca-frequency-biweekly = Every two weeks
+dca-frequency-monthly = Monthly
+dca-plan-title = {$symbol}: {$amount} {$currency}, {$frequency} since {$start}
+dca-plan-status = Done: {$done} · Due: {$due} · Overdue: {$overdue}
+dca-plan-next-buy = Next buy: {$date}
+dca-plan-overdue-dates = Missed: {$dates}
+dca-scenario = {$change}/year
+dca-projection = {$scenario} in {$months} months: {$buys} buys, cost {$cost} USD, holding {$amount}, worth {$value} USD
+dca-projection-usd-only = Projections are only available for plans in USD
+dca-projection-no-price = Projections need the coin's current price
+error-invalid-dca-plan = Invalid DCA plan
+dca-error-missing-symbol = The coin symbol is required
+dca-error-invalid-amount = The amount must be a positive number
+dca-error-invalid-start-date = The start date must look like 2024-01-31
+tab-performance = Performance
+benchmark-load = Load price history
+benchmark-loading = Loading price history...
+benchmark-portfolio = Portfolio
+benchmark-btc = All in BTC
+benchmark-eth = All in ETH
+benchmark-fiat = Kept in USD
+benchmark-row = {$name}: invested {$invested} USD, worth {$value} USD, time-weighted {$twr}, money-weighted {$mwr}/year
+benchmark-row-na = {$name}: missing prices
+error-fetching-historical-prices = Fetching price history failed
+export-beancount = Export Beancount
+export-hledger = Export hledger
+import-journal = Import journal
+export-journal-dialog-title = Export the paid entries as a journal
+import-journal-dialog-title = Pick a Beancount or hledger journal
+save-imported-json-dialog-title = Save the imported entries as JSON
+journal-exported = Journal exported
+journal-exported-body = Saved to {$path}, {$skipped} undated or empty entries left out
+error-journal-file = Reading or writing the journal failed
+tab-wallets = Wallets
+bitcoin-explorer-url = Bitcoin explorer (Esplora) URL
+evm-explorer-url = EVM explorer (Etherscan) URL
+evm-explorer-api-key = EVM explorer API key
+explorer-settings-save = Save explorer settings
+wallet-address = Address
+wallet-label = Label
+wallet-add = Add wallet
+wallet-balances-refresh = Fetch balances
+wallet-balances-fetching = Fetching balances...
+wallet-balance-na = Balance: N/A
+wallet-balance-error = Balance unavailable: {$error}
+wallet-reconciliation = {$symbol}: {$recorded} recorded, {$on_chain} on-chain, {$status}
+wallet-reconciliation-ok = reconciled
+wallet-reconciliation-mismatch = ⚠ discrepancy
+exclude-stable-coins-from-pnl = Exclude stablecoins from P&L
+depeg-band-percent = Depeg band (%)
+portfolio-summary = Cost {$cost} USD · Value {$value} USD · P&L {$pnl} USD · Cash-like {$cash} USD
+depeg-warning = ⚠ {$symbol} trades at {$price} USD, {$deviation} off its peg
+holdings-coins = Coins
+holdings-cash-like = Cash-like (stablecoins)
+holding-row = {$symbol}: {$amount}, worth {$value} USD
+holding-row-pnl = {$symbol}: {$amount}, cost {$cost} USD, worth {$value} USD, P&L {$pnl} USD
+holding-row-no-price = {$symbol}: {$amount}, no price
+holdings-search = Search coins
+holdings-dust-threshold = Hide below (USD)
+holdings-group-by-platform = Group by platform
+filter-all-platforms = All platforms
+platform-native = Native chains
+filter-stable-coins-all = Stablecoins: shown
+filter-stable-coins-only = Stablecoins only
+filter-stable-coins-exclude = Stablecoins: hidden
+filter-profit-all = Profit and loss
+filter-profit-in-profit = In profit
+filter-profit-at-loss = At a loss
+fees-summary = Fees paid: {$fees} USD ({$allocation})
+fee-allocation-capitalize = Fees added to cost basis
+fee-allocation-expense = Fees counted as expenses
+fees-unvalued = {$count ->
+    [one] A fee paid in another coin has no USD value and is left out
+   *[other] {$count} fees paid in other coins have no USD value and are left out
+}
+export-report = Export report
+export-report-dialog-title = Export portfolio report
+report-exported = Report exported
+report-exported-body = Saved to {$path}
+error-exporting-report = Error exporting report
+report-title = Portfolio report {$date}
+report-symbol = Symbol
+report-amount = Amount
+report-price = Price
+report-cost = Cost basis
+report-value = Value
+report-pnl = P&L
+report-cash-like = Cash-like
+report-fees = Fees
+demo-mode = Demo mode
+demo-mode-description = A sample portfolio priced from bundled quotes, nothing is fetched
+world-clock = World clock
+world-clock-add = Add a time zone
+world-clock-search = Search by city or region
+world-clock-zone = {$zone}, {$offset}, {$days ->
+    [0] today
+    [1] tomorrow
+    [-1] yesterday
+   *[other] {$days} days
+}
+clock-24-hour = 24-hour clock
+clock-show-date = Show date
+clock-custom-pattern = Custom format (strftime)
+clock-custom-pattern-invalid = Not a valid strftime pattern
+tab-clock = Clock
+tab-stopwatch = Stopwatch
+stopwatch-start = Start
+stopwatch-pause = Pause
+stopwatch-reset = Reset
+stopwatch-lap = Lap
+stopwatch-lap-number = Lap {$number}
+stopwatch-lap-time = Lap time
+stopwatch-split-time = Split time
+stopwatch-laps-copy = Copy laps as CSV
+stopwatch-laps-export = Export laps
+stopwatch-laps-export-dialog-title = Export laps as CSV
+stopwatch-laps-exported = Laps saved to {$path}
+stopwatch-laps-export-failed = Couldn't export laps: {$reason}
+tab-timers = Timers
+timer-name = Name
+timer-name-placeholder = Tea
+timer-default-name = Timer {$number}
+timer-duration = Duration
+timer-duration-invalid = Use minutes, MM:SS or HH:MM:SS, up to 99:59:59
+timer-sound = Sound
+timer-add = Start timer
+timer-of = of {$duration}
+timer-done = Done
+timer-finished = Time's up
+tab-alarms = Alarms
+alarm = Alarm
+alarm-time = Time
+alarm-time-invalid = Enter the time as HH:MM
+alarm-label = Label
+alarm-snooze-minutes = Snooze minutes
+alarm-add = Add alarm
+alarm-once = Once
+alarm-every-day = Every day
+alarm-snoozed-until = Snoozed until {$time}
+alarm-snooze = Snooze
+alarm-dismiss = Dismiss
+alarm-label-too-long = Labels can be up to {$max} characters
+alarm-duplicate-weekday = {$weekday} is repeated
+alarm-snooze-out-of-range = Snooze for 1 to {$max} minutes
+tab-pomodoro = Pomodoro
+pomodoro-work = Focus
+pomodoro-short-break = Short break
+pomodoro-long-break = Long break
+pomodoro-work-minutes = Focus minutes
+pomodoro-short-break-minutes = Short break minutes
+pomodoro-long-break-minutes = Long break minutes
+pomodoro-sessions-per-cycle = Sessions before a long break
+pomodoro-setting-invalid = Lengths are 1 to 240 minutes, with up to 12 sessions before a long break
+pomodoro-start = Start
+pomodoro-pause = Pause
+pomodoro-skip = Skip
+pomodoro-reset = Reset
+pomodoro-session = Session {$number} of {$total}
+pomodoro-work-over = Focus session done
+pomodoro-break-over = Break's over
+pomodoro-next = {$phase} for {$minutes} {$minutes ->
+    [one] minute
+   *[other] minutes
+}
+pomodoro-stats = Focus time
+pomodoro-today = Today: {$sessions} {$sessions ->
+    [one] session
+   *[other] sessions
+}, {$minutes} min
+pomodoro-this-week = This week: {$sessions} {$sessions ->
+    [one] session
+   *[other] sessions
+}, {$minutes} min
+pomodoro-log-error = Couldn't read or write the focus session log: {$reason}
+clock-face-analog = Analog
+clock-face-digital = Digital
+clock-face-both = Analog and digital
+ntp-clock-drift = Clock drift
+ntp-server = NTP server
+ntp-warning-threshold = Warn above (ms)
+ntp-warning-threshold-invalid = Enter the threshold in whole milliseconds
+ntp-check = Check
+ntp-checking = Checking…
+ntp-result = Offset {$offset}, round trip {$delay}, stratum {$stratum}
+ntp-offset-warning = This clock is off by more than {$threshold} ms
+ntp-error = Couldn't check the clock: {$reason}
+tab-converter = Time zones
+converter-date = Date
+converter-time = Time
+converter-now = Now
+converter-local-zone = Local
+converter-input-invalid = Enter the date as YYYY-MM-DD and the time as HH:MM
+converter-time-skipped = That time doesn't exist in the chosen zone, the clocks skip it
+converter-planner = Meeting planner
+tab-timestamp = Timestamps
+timestamp-input = Timestamp
+timestamp-now = Now
+timestamp-detected = Read as {$format}
+timestamp-unrecognized = Paste epoch seconds or milliseconds, or an RFC 3339 or RFC 2822 date
+timestamp-epoch-seconds = Unix epoch, seconds
+timestamp-epoch-millis = Unix epoch, milliseconds
+timestamp-rfc3339 = RFC 3339
+timestamp-rfc2822 = RFC 2822
+timestamp-rfc3339-utc = RFC 3339, UTC
+timestamp-rfc3339-local = RFC 3339, local time
+timestamp-rfc2822-utc = RFC 2822, UTC
+timestamp-rfc2822-local = RFC 2822, local time
+timestamp-na = N/A
+calendar-today = Today
+calendar-week = Wk
+calendar-first-weekday = Weeks start on
+calendar-first-weekday-locale = Locale default
+calendar-holiday = {$day}: {$name}
+calendar-import-holidays = Import holidays…
+calendar-import-dialog-title = Import holidays from an ICS file
+calendar-clear-holidays = Clear holidays
+calendar-holidays-imported = {$count ->
+    [one] Imported 1 holiday
+   *[other] Imported {$count} holidays
+}
+calendar-import-failed = Couldn't import holidays: {$reason}
+sun = Sun
+sun-latitude = Latitude
+sun-longitude = Longitude
+sun-coordinates-hint = Enter the latitude and longitude in decimal degrees, north and east positive
+sun-civil-dawn = Civil dawn
+sun-sunrise = Sunrise
+sun-solar-noon = Solar noon
+sun-sunset = Sunset
+sun-civil-dusk = Civil dusk
+sun-day-length = Day length
+sun-day-length-value = {$hours} h {$minutes} min
+sun-always-above = Sun up all day
+sun-always-below = Sun down all day
+about-pc-na = N/A
+about-pc-machine = Machine
+about-pc-hostname = Hostname
+about-pc-kernel = Kernel
+about-pc-uptime = Uptime
+about-pc-uptime-value = {$days ->
+    [0] {$time}
+    [one] 1 day, {$time}
+   *[other] {$days} days, {$time}
+}
+about-pc-cpu = CPU
+about-pc-cpu-value = {$model} ({$cores} cores, {$threads} threads)
+about-pc-memory = Memory
+about-pc-swap = Swap
+about-pc-mount = {$mount_point} ({$fs_type})
+about-pc-available-of-total = {$available} free of {$total}
+about-pc-live-usage = Live usage
+about-pc-core-usage = CPU {$core}: {$percent}%
+about-pc-used-percent = {$label}: {$percent}% used
+about-pc-throughput = {$interface}: ↓ {$received}/s, ↑ {$sent}/s
+about-pc-percent = {$percent}%
+about-pc-battery = Battery
+about-pc-battery-charge = Charge
+about-pc-battery-status = State
+about-pc-battery-until-full = Until full
+about-pc-battery-until-empty = Remaining
+about-pc-battery-health = Health (full versus design capacity)
+about-pc-battery-cycles = Charge cycles
+about-pc-battery-charging = Charging
+about-pc-battery-discharging = Discharging
+about-pc-battery-full = Full
+about-pc-battery-not-charging = Plugged in, not charging
+about-pc-battery-unknown = Unknown
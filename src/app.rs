@@ -93,7 +93,10 @@ impl Application for AppModel {
             config_handler: flags.config_handler.clone(),
             config: app_config.clone(),
             about_pc_page: pages::about_pc::AboutPcPage::default(),
-            clock_page: pages::clock::ClockPage::default(),
+            clock_page: pages::clock::ClockPage::new(
+                app_config.clone(),
+                flags.config_handler.clone(),
+            ),
             preferences_page: pages::preferences::PreferencesPage::new(
                 app_config.clone(),
                 flags.config_handler.clone(),
@@ -170,13 +173,24 @@ impl Application for AppModel {
     /// Register subscriptions for this application.
     ///
     /// Subscriptions are long-running async tasks running in the background which
-    /// emit messages to the application through a channel. They are started at the
-    /// beginning of the application, and persist through its lifetime.
+    /// emit messages to the application through a channel. Only the active page's are
+    /// kept, plus whatever the Clock page still needs while it's hidden, so an idle
+    /// app isn't woken up every second.
     fn subscription(&self) -> Subscription<Self::Message> {
+        let page_subscription = match self.nav.active_data::<Page>() {
+            Some(Page::AboutPc) | None => self.about_pc_page.subscription().map(Into::into),
+            Some(Page::Clock) => self.clock_page.subscription().map(Into::into),
+            Some(Page::Preferences) => self.preferences_page.subscription().map(Into::into),
+            Some(Page::PaidEntries) => self.paid_entries_page.subscription().map(Into::into),
+        };
+        let clock_background_subscription = match self.nav.active_data::<Page>() {
+            Some(Page::Clock) => Subscription::none(),
+            _ => self.clock_page.background_subscription().map(Into::into),
+        };
+
         Subscription::batch(vec![
-            self.about_pc_page.subscription().map(Into::into),
-            self.clock_page.subscription().map(Into::into),
-            self.preferences_page.subscription().map(Into::into),
+            page_subscription,
+            clock_background_subscription,
             // Watch for application configuration changes.
             self.core()
                 .watch_config::<UniConfig>(Self::APP_ID)
@@ -226,7 +240,10 @@ impl Application for AppModel {
                     _ = self.about_pc_page.update(about_pc_page_message);
                 }
                 pages::Message::Clock(clock_page_message) => {
-                    _ = self.clock_page.update(clock_page_message);
+                    return self
+                        .clock_page
+                        .update(clock_page_message)
+                        .map(|task| UniAppMessage::Page(pages::Message::Clock(task)).into())
                 }
                 pages::Message::Preferences(config_page_message) => {
                     _ = self.preferences_page.update(config_page_message);
@@ -327,6 +344,10 @@ impl AppModel {
 
     pub fn on_page_init(&self) -> Task<UniAppMessage> {
         match self.nav.active_data::<Page>() {
+            Some(Page::Clock) => self
+                .clock_page
+                .on_init()
+                .map(|it| pages::Message::Clock(it).into()),
             Some(Page::PaidEntries) => self
                 .paid_entries_page
                 .on_init()
@@ -10,7 +10,13 @@ use cosmic::{
 };
 
 use crate::app::{AppModel, Flags};
-use crate::pages::Page;
+use crate::pages::{
+    clock::{
+        Alarm, ClockFace, ClockFormat, Coordinates, CountdownTimer, Holiday, PomodoroSettings,
+    },
+    paid_entries::{DcaPlan, FeeAllocation, HoldingsFilter, WalletAddress},
+    Page,
+};
 
 #[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
 #[version = 1]
@@ -19,6 +25,34 @@ pub struct UniConfig {
     pub last_active_page: Page,
     pub paid_entries_json_path: Option<PathBuf>,
     pub coin_market_cap_api_key: Option<String>,
+    pub dca_plans: Vec<DcaPlan>,
+    pub wallets: Vec<WalletAddress>,
+    pub bitcoin_explorer_url: Option<String>,
+    pub evm_explorer_url: Option<String>,
+    pub evm_explorer_api_key: Option<String>,
+    pub exclude_stable_coins_from_pnl: bool,
+    /// `None` uses the built-in band.
+    pub stablecoin_depeg_band_bps: Option<u32>,
+    pub holdings_filter: HoldingsFilter,
+    pub fee_allocation: FeeAllocation,
+    /// Shows the bundled sample portfolio, priced offline, instead of the JSON file.
+    pub demo_mode: bool,
+    /// IANA names, in the order the world clock lists them.
+    pub world_clock_zones: Vec<String>,
+    pub clock_format: ClockFormat,
+    pub clock_face: ClockFace,
+    pub countdown_timers: Vec<CountdownTimer>,
+    pub alarms: Vec<Alarm>,
+    pub pomodoro_settings: PomodoroSettings,
+    /// A host name or address, with an optional port. `None` uses the built-in server.
+    pub ntp_server: Option<String>,
+    /// `None` uses the built-in threshold.
+    pub ntp_warning_threshold_ms: Option<u32>,
+    /// `None` follows the locale.
+    pub calendar_first_weekday: Option<chrono::Weekday>,
+    pub calendar_holidays: Vec<Holiday>,
+    /// Where sunrise and sunset are worked out for, `None` until it's set.
+    pub sun_coordinates: Option<Coordinates>,
 }
 
 pub const CONFIG_VERSION: u64 = 1;
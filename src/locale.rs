@@ -1,15 +1,48 @@
-use chrono::{format, Locale};
+use chrono::{format, Locale, Weekday};
 use std::str::FromStr;
 
-pub fn get_locale() -> Locale {
+/// The `LC_TIME` locale, or `LANG`'s, without its encoding, such as `en_US`.
+fn locale_name() -> Option<String> {
     let locale = std::env::var("LC_TIME").or_else(|_| std::env::var("LANG"));
 
-    if let Some(locale) = locale
+    locale
         .ok()
         .and_then(|locale| locale.split('.').next().map(ToString::to_string))
-    {
+}
+
+pub fn get_locale() -> Locale {
+    if let Some(locale) = locale_name() {
         format::Locale::from_str(&locale).unwrap_or(format::Locale::POSIX)
     } else {
         format::Locale::POSIX
     }
 }
+
+/// The day weeks start on in the locale's region, per CLDR's week data. Monday when
+/// there's no region, as in ISO 8601.
+pub fn get_first_weekday() -> Weekday {
+    locale_name()
+        .as_deref()
+        .and_then(|locale| locale.split(['_', '@']).nth(1))
+        .map_or(Weekday::Mon, first_weekday_of_region)
+}
+
+fn first_weekday_of_region(region: &str) -> Weekday {
+    const SUNDAY_FIRST: [&str; 51] = [
+        "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET", "GT",
+        "GU", "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO",
+        "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV",
+        "TH", "TT", "TW", "US", "VE", "ZA",
+    ];
+    const SATURDAY_FIRST: [&str; 15] = [
+        "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+    ];
+
+    if SUNDAY_FIRST.contains(&region) {
+        Weekday::Sun
+    } else if SATURDAY_FIRST.contains(&region) {
+        Weekday::Sat
+    } else {
+        Weekday::Mon
+    }
+}
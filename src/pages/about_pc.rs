@@ -1,24 +1,47 @@
+mod battery;
+mod graph;
+mod monitor;
+mod system;
+
+use std::{collections::VecDeque, path::Path, time::Duration};
+
 use crate::fl;
 use crate::{app, pages};
+use battery::{Battery, BatteryStatus};
 use cosmic::iced::Subscription;
 use cosmic::widget;
 use cosmic::{
+    cosmic_theme,
     iced::{alignment::Horizontal, Alignment, Length},
     theme,
 };
 use cosmic::{prelude::*, Task};
 use futures_util::SinkExt as _;
+use graph::Graph;
+use monitor::{History, Sample};
+use system::SystemOverview;
+
+const OVERVIEW_MAX_WIDTH: f32 = 720.0;
+const CORE_GRAPH_WIDTH: f32 = 160.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+/// Network graphs never scale below this many bytes a second, so idle links stay flat.
+const MIN_THROUGHPUT_SCALE: f32 = 1024.0;
 
 #[derive(Default)]
 pub struct AboutPcPage {
     realname: Option<String>,
     distro: Option<String>,
+    system_overview: Option<SystemOverview>,
+    history: History,
+    batteries: Vec<Battery>,
 }
 
 #[derive(Debug, Clone)]
 pub enum AboutPcPageMessage {
     FetchedRealname(String),
     FetchedDistro(String),
+    FetchedSystemOverview(SystemOverview),
+    Sampled(Sample, Vec<Battery>),
 }
 
 impl From<AboutPcPageMessage> for app::UniAppMessage {
@@ -27,6 +50,267 @@ impl From<AboutPcPageMessage> for app::UniAppMessage {
     }
 }
 
+impl AboutPcPage {
+    fn view_system_overview(
+        system_overview: &SystemOverview,
+    ) -> cosmic::Element<AboutPcPageMessage> {
+        let na = || fl!("about-pc-na");
+        let cpu = system_overview.cpu.as_ref().map_or_else(na, |cpu| {
+            fl!(
+                "about-pc-cpu-value",
+                model = cpu.model.clone(),
+                cores = cpu.physical_cores,
+                threads = cpu.logical_cores
+            )
+        });
+        let (memory, swap) = system_overview.memory.map_or_else(
+            || (na(), na()),
+            |memory| {
+                (
+                    fl!(
+                        "about-pc-available-of-total",
+                        available = system::format_bytes(memory.available_bytes),
+                        total = system::format_bytes(memory.total_bytes)
+                    ),
+                    fl!(
+                        "about-pc-available-of-total",
+                        available = system::format_bytes(memory.swap_free_bytes),
+                        total = system::format_bytes(memory.swap_total_bytes)
+                    ),
+                )
+            },
+        );
+
+        let rows = [
+            (fl!("about-pc-machine"), system_overview.machine.clone()),
+            (fl!("about-pc-hostname"), system_overview.hostname.clone()),
+            (fl!("about-pc-kernel"), system_overview.kernel.clone()),
+            (
+                fl!("about-pc-uptime"),
+                system_overview.uptime.map(format_uptime),
+            ),
+            (fl!("about-pc-cpu"), Some(cpu)),
+            (fl!("about-pc-memory"), Some(memory)),
+            (fl!("about-pc-swap"), Some(swap)),
+        ]
+        .into_iter()
+        .chain(system_overview.mounts.iter().map(|mount_usage| {
+            (
+                fl!(
+                    "about-pc-mount",
+                    mount_point = mount_usage.mount.mount_point.display().to_string(),
+                    fs_type = mount_usage.mount.fs_type.clone()
+                ),
+                Some(fl!(
+                    "about-pc-available-of-total",
+                    available = system::format_bytes(mount_usage.available_bytes),
+                    total = system::format_bytes(mount_usage.total_bytes)
+                )),
+            )
+        }));
+
+        rows.fold(widget::list_column(), |list, (label, value)| {
+            list.add(
+                widget::row()
+                    .push(widget::text(label).width(Length::Fill))
+                    .push(widget::text::monotext(value.unwrap_or_else(na))),
+            )
+        })
+        .apply(widget::container)
+        .max_width(OVERVIEW_MAX_WIDTH)
+        .into()
+    }
+
+    fn view_batteries(&self) -> cosmic::Element<AboutPcPageMessage> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+        let na = || fl!("about-pc-na");
+
+        self.batteries
+            .iter()
+            .fold(
+                widget::column()
+                    .spacing(space_s)
+                    .push(widget::text::title3(fl!("about-pc-battery"))),
+                |column, battery| {
+                    let rows = [
+                        (
+                            fl!("about-pc-battery-charge"),
+                            battery.charge_percent.map(|charge_percent| {
+                                fl!("about-pc-percent", percent = charge_percent)
+                            }),
+                        ),
+                        (
+                            fl!("about-pc-battery-status"),
+                            Some(battery_status_label(battery.status)),
+                        ),
+                        (
+                            match battery.status {
+                                BatteryStatus::Charging => fl!("about-pc-battery-until-full"),
+                                _ => fl!("about-pc-battery-until-empty"),
+                            },
+                            battery.time_remaining.map(format_time_remaining),
+                        ),
+                        (
+                            fl!("about-pc-battery-health"),
+                            battery.health_percent.map(|health_percent| {
+                                fl!("about-pc-percent", percent = health_percent)
+                            }),
+                        ),
+                        (
+                            fl!("about-pc-battery-cycles"),
+                            battery
+                                .cycle_count
+                                .map(|cycle_count| cycle_count.to_string()),
+                        ),
+                    ];
+
+                    column
+                        .push(widget::text::heading(battery.name.clone()))
+                        .push(rows.into_iter().fold(
+                            widget::list_column(),
+                            |list, (label, value)| {
+                                list.add(
+                                    widget::row()
+                                        .push(widget::text(label).width(Length::Fill))
+                                        .push(widget::text::monotext(value.unwrap_or_else(na))),
+                                )
+                            },
+                        ))
+                },
+            )
+            .apply(widget::container)
+            .max_width(OVERVIEW_MAX_WIDTH)
+            .into()
+    }
+
+    fn view_usage(&self) -> cosmic::Element<AboutPcPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let latest_percent =
+            |history: &VecDeque<f32>| history.back().map_or(0.0, |share| share * 100.0);
+        let graph = |series: Vec<Vec<f32>>, max: f32, width: Length| {
+            let graph: cosmic::Element<'static, AboutPcPageMessage> =
+                cosmic::iced::widget::canvas(Graph { series, max })
+                    .width(width)
+                    .height(Length::Fixed(GRAPH_HEIGHT))
+                    .into();
+
+            graph
+        };
+        let labelled = |label: String, graph: cosmic::Element<'static, AboutPcPageMessage>| {
+            widget::column()
+                .spacing(space_xxs)
+                .push(widget::text::caption(label))
+                .push(graph)
+        };
+
+        let cores = widget::flex_row(
+            self.history
+                .cores
+                .iter()
+                .enumerate()
+                .map(|(index, history)| {
+                    labelled(
+                        fl!(
+                            "about-pc-core-usage",
+                            core = index,
+                            percent = format!("{:.0}", latest_percent(history))
+                        ),
+                        graph(
+                            vec![history.iter().copied().collect()],
+                            1.0,
+                            Length::Fixed(CORE_GRAPH_WIDTH),
+                        ),
+                    )
+                    .into()
+                })
+                .collect(),
+        )
+        .row_spacing(space_s)
+        .column_spacing(space_s);
+
+        let memory = [
+            (fl!("about-pc-memory"), &self.history.memory),
+            (fl!("about-pc-swap"), &self.history.swap),
+        ]
+        .into_iter()
+        .map(|(label, history)| {
+            labelled(
+                fl!(
+                    "about-pc-used-percent",
+                    label = label,
+                    percent = format!("{:.0}", latest_percent(history))
+                ),
+                graph(vec![history.iter().copied().collect()], 1.0, Length::Fill),
+            )
+        });
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let interfaces = self.history.interfaces.iter().map(|(name, history)| {
+            let received: Vec<f32> = history.iter().map(|it| it.received as f32).collect();
+            let sent: Vec<f32> = history.iter().map(|it| it.sent as f32).collect();
+            let max = received
+                .iter()
+                .chain(&sent)
+                .copied()
+                .fold(MIN_THROUGHPUT_SCALE, f32::max);
+            let latest = history.back().copied().unwrap_or_default();
+
+            labelled(
+                fl!(
+                    "about-pc-throughput",
+                    interface = name.clone(),
+                    received = system::format_bytes(latest.received as u64),
+                    sent = system::format_bytes(latest.sent as u64)
+                ),
+                graph(vec![received, sent], max, Length::Fill),
+            )
+        });
+
+        memory
+            .chain(interfaces)
+            .fold(
+                widget::column()
+                    .spacing(space_s)
+                    .push(widget::text::title3(fl!("about-pc-live-usage")))
+                    .push(cores),
+                |column, section| column.push(section),
+            )
+            .apply(widget::container)
+            .max_width(OVERVIEW_MAX_WIDTH)
+            .into()
+    }
+}
+
+fn battery_status_label(status: BatteryStatus) -> String {
+    match status {
+        BatteryStatus::Charging => fl!("about-pc-battery-charging"),
+        BatteryStatus::Discharging => fl!("about-pc-battery-discharging"),
+        BatteryStatus::Full => fl!("about-pc-battery-full"),
+        BatteryStatus::NotCharging => fl!("about-pc-battery-not-charging"),
+        BatteryStatus::Unknown => fl!("about-pc-battery-unknown"),
+    }
+}
+
+/// `2:30`
+fn format_time_remaining(time_remaining: Duration) -> String {
+    let minutes = time_remaining.as_secs() / 60;
+
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// `3 days, 2:03`
+fn format_uptime(uptime: Duration) -> String {
+    let minutes = uptime.as_secs() / 60;
+
+    fl!(
+        "about-pc-uptime-value",
+        days = minutes / (24 * 60),
+        time = format!("{}:{:02}", minutes / 60 % 24, minutes % 60)
+    )
+}
+
 #[allow(clippy::unused_self)]
 impl pages::IPage<AboutPcPageMessage> for AboutPcPage {
     fn view(&self) -> cosmic::Element<AboutPcPageMessage> {
@@ -52,17 +336,55 @@ impl pages::IPage<AboutPcPageMessage> for AboutPcPage {
                     std::env::consts::OS,
                     std::env::consts::ARCH
                 )))
+                .push_maybe(
+                    self.system_overview
+                        .as_ref()
+                        .map(Self::view_system_overview),
+                )
+                .push_maybe((!self.batteries.is_empty()).then(|| self.view_batteries()))
+                .push(self.view_usage())
                 .spacing(theme::active().cosmic().space_m())
                 .align_x(Alignment::Center),
         )
-        .center(Length::Fill)
+        // Scrolls once the overview is taller than the window
+        .center_x(Length::Fill)
+        .apply(widget::scrollable)
         .into()
     }
 
     fn subscription(&self) -> Subscription<AboutPcPageMessage> {
         struct WhoamiSubscription;
+        struct MonitorSubscription;
 
-        Subscription::run_with_id(
+        let monitor = Subscription::run_with_id(
+            std::any::TypeId::of::<MonitorSubscription>(),
+            cosmic::iced::stream::channel(
+                std::mem::size_of::<AboutPcPageMessage>(),
+                move |mut channel| async move {
+                    loop {
+                        match tokio::task::spawn_blocking(|| {
+                            (
+                                monitor::read_sample(),
+                                battery::read_batteries(Path::new(battery::POWER_SUPPLY_DIR)),
+                            )
+                        })
+                        .await
+                        {
+                            Ok((sample, batteries)) => {
+                                _ = channel
+                                    .feed(AboutPcPageMessage::Sampled(sample, batteries))
+                                    .await;
+                            }
+                            Err(e) => tracing::error!("read_sample failed: {e}"),
+                        }
+
+                        tokio::time::sleep(monitor::SAMPLE_INTERVAL).await;
+                    }
+                },
+            ),
+        );
+
+        let whoami = Subscription::run_with_id(
             std::any::TypeId::of::<WhoamiSubscription>(),
             cosmic::iced::stream::channel(
                 std::mem::size_of::<AboutPcPageMessage>(),
@@ -74,9 +396,20 @@ impl pages::IPage<AboutPcPageMessage> for AboutPcPage {
                     _ = channel
                         .feed(AboutPcPageMessage::FetchedDistro(whoami::distro()))
                         .await;
+
+                    match tokio::task::spawn_blocking(system::read_overview).await {
+                        Ok(system_overview) => {
+                            _ = channel
+                                .feed(AboutPcPageMessage::FetchedSystemOverview(system_overview))
+                                .await;
+                        }
+                        Err(e) => tracing::error!("read_overview failed: {e}"),
+                    }
                 },
             ),
-        )
+        );
+
+        Subscription::batch([whoami, monitor])
     }
 
     fn update(&mut self, message: AboutPcPageMessage) -> Task<AboutPcPageMessage> {
@@ -85,6 +418,13 @@ impl pages::IPage<AboutPcPageMessage> for AboutPcPage {
                 self.realname = Some(realname);
             }
             AboutPcPageMessage::FetchedDistro(distro) => self.distro = Some(distro),
+            AboutPcPageMessage::FetchedSystemOverview(system_overview) => {
+                self.system_overview = Some(system_overview);
+            }
+            AboutPcPageMessage::Sampled(sample, batteries) => {
+                self.history.push(sample);
+                self.batteries = batteries;
+            }
         }
         Task::none()
     }
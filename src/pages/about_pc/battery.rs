@@ -0,0 +1,179 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    Full,
+    /// Plugged in but held below full, as charge thresholds do.
+    NotCharging,
+    Unknown,
+}
+
+impl BatteryStatus {
+    fn parse(status: &str) -> Self {
+        match status {
+            "Charging" => Self::Charging,
+            "Discharging" => Self::Discharging,
+            "Full" => Self::Full,
+            "Not charging" => Self::NotCharging,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A system battery, with what its driver reports. Drivers report either energy, in
+/// µWh and µW, or charge, in µAh and µA, and the ratios work out the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Battery {
+    pub name: String,
+    pub status: BatteryStatus,
+    pub charge_percent: Option<u8>,
+    pub time_remaining: Option<Duration>,
+    /// How much of its design capacity it still holds, from 0 to 100.
+    pub health_percent: Option<u8>,
+    pub cycle_count: Option<u32>,
+}
+
+/// The batteries powering the machine, leaving out mains adapters and the batteries
+/// of devices such as mice, which the driver scopes to `Device`. Empty when there's
+/// no battery, or no sysfs.
+#[must_use]
+pub fn read_batteries(power_supply_dir: &Path) -> Vec<Battery> {
+    let Ok(entries) = std::fs::read_dir(power_supply_dir) else {
+        return vec![];
+    };
+
+    let mut supply_dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    // read_dir's order isn't stable
+    supply_dirs.sort();
+
+    supply_dirs
+        .iter()
+        .filter_map(|supply_dir| read_battery(supply_dir))
+        .collect()
+}
+
+fn read_battery(supply_dir: &Path) -> Option<Battery> {
+    let read = |name: &str| {
+        std::fs::read_to_string(supply_dir.join(name))
+            .ok()
+            .map(|value| value.trim().to_owned())
+    };
+    let read_number = |name: &str| read(name)?.parse::<u64>().ok();
+
+    if read("type")? != "Battery" || read("scope").is_some_and(|scope| scope == "Device") {
+        return None;
+    }
+
+    let status = read("status").map_or(BatteryStatus::Unknown, |status| {
+        BatteryStatus::parse(&status)
+    });
+    let (now, full, full_design, rate) = if read_number("energy_full").is_some() {
+        (
+            read_number("energy_now"),
+            read_number("energy_full"),
+            read_number("energy_full_design"),
+            read_number("power_now"),
+        )
+    } else {
+        (
+            read_number("charge_now"),
+            read_number("charge_full"),
+            read_number("charge_full_design"),
+            read_number("current_now"),
+        )
+    };
+
+    Some(Battery {
+        name: supply_dir.file_name()?.to_string_lossy().into_owned(),
+        charge_percent: read_number("capacity")
+            .or_else(|| percent(now?, full?))
+            .and_then(|capacity| u8::try_from(capacity.min(100)).ok()),
+        time_remaining: time_remaining(status, now, full, rate),
+        health_percent: full
+            .zip(full_design)
+            .and_then(|(full, full_design)| percent(full, full_design))
+            .and_then(|health| u8::try_from(health.min(100)).ok()),
+        // Drivers that don't count cycles report 0
+        cycle_count: read_number("cycle_count")
+            .filter(|cycle_count| *cycle_count > 0)
+            .and_then(|cycle_count| u32::try_from(cycle_count).ok()),
+        status,
+    })
+}
+
+fn percent(part: u64, whole: u64) -> Option<u64> {
+    (whole > 0).then(|| part.saturating_mul(100) / whole)
+}
+
+/// Until empty when discharging, until full when charging, at the current rate.
+fn time_remaining(
+    status: BatteryStatus,
+    now: Option<u64>,
+    full: Option<u64>,
+    rate: Option<u64>,
+) -> Option<Duration> {
+    let rate = rate.filter(|rate| *rate > 0)?;
+    let remaining = match status {
+        BatteryStatus::Discharging => now?,
+        BatteryStatus::Charging => full?.saturating_sub(now?),
+        BatteryStatus::Full | BatteryStatus::NotCharging | BatteryStatus::Unknown => return None,
+    };
+
+    Some(Duration::from_secs(remaining.saturating_mul(3600) / rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use super::{read_batteries, Battery, BatteryStatus};
+
+    fn fixture(path: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("resources/fixtures")
+            .join(path)
+    }
+
+    #[test]
+    fn reads_system_batteries_only() {
+        let batteries = read_batteries(&fixture("sys/class/power_supply"));
+
+        assert_eq!(
+            batteries,
+            [
+                Battery {
+                    name: "BAT0".to_owned(),
+                    status: BatteryStatus::Discharging,
+                    charge_percent: Some(62),
+                    // 31.0 Wh at 12.4 W
+                    time_remaining: Some(Duration::from_mins(150)),
+                    health_percent: Some(88),
+                    cycle_count: Some(312),
+                },
+                Battery {
+                    name: "BAT1".to_owned(),
+                    status: BatteryStatus::Charging,
+                    charge_percent: Some(40),
+                    // 1.2 Ah to go at 0.8 A
+                    time_remaining: Some(Duration::from_mins(90)),
+                    health_percent: Some(100),
+                    cycle_count: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_batteries_without_sysfs() {
+        assert_eq!(read_batteries(&fixture("sys/class/no_such_dir")), []);
+    }
+}
@@ -0,0 +1,73 @@
+use cosmic::{
+    iced::{
+        mouse,
+        widget::canvas::{self, Path, Stroke},
+        Color, Point, Rectangle,
+    },
+    Renderer, Theme,
+};
+
+use super::monitor::HISTORY_LENGTH;
+
+/// A canvas program drawing rolling histories as lines, oldest on the left and the
+/// latest at the right edge, with `max` at the top.
+pub struct Graph {
+    /// Up to two of them, drawn in the accent and then the success color.
+    pub series: Vec<Vec<f32>>,
+    pub max: f32,
+}
+
+impl<Message> canvas::Program<Message, Theme> for Graph {
+    type State = ();
+
+    #[allow(clippy::cast_precision_loss)]
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let cosmic = theme.cosmic();
+        let line_colors = [
+            Color::from(cosmic.accent_color()),
+            Color::from(cosmic.success.base),
+        ];
+
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let (width, height) = (frame.width(), frame.height());
+        let step = width / (HISTORY_LENGTH - 1) as f32;
+
+        frame.fill_rectangle(
+            Point::ORIGIN,
+            frame.size(),
+            Color::from(cosmic.primary.base),
+        );
+
+        for (values, color) in self.series.iter().zip(line_colors) {
+            // Short histories start part of the way in
+            let offset = HISTORY_LENGTH.saturating_sub(values.len()) as f32;
+            let line = Path::new(|builder| {
+                for (index, value) in values.iter().enumerate() {
+                    let share = if self.max > 0.0 {
+                        (value / self.max).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    let point = Point::new((offset + index as f32) * step, height - share * height);
+
+                    if index == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            });
+
+            frame.stroke(&line, Stroke::default().with_color(color).with_width(2.0));
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
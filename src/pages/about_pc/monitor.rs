@@ -0,0 +1,268 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use super::system::{self, MemoryInfo};
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// How many samples the graphs keep, a minute's worth.
+pub const HISTORY_LENGTH: usize = 60;
+
+/// Jiffies a core has spent busy, and in total, since boot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+/// Bytes an interface has received and sent since it came up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterfaceBytes {
+    pub received: u64,
+    pub sent: u64,
+}
+
+/// One reading of /proc/stat, /proc/meminfo and /proc/net/dev.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub taken_at: Instant,
+    pub cores: Vec<CpuTimes>,
+    pub memory: Option<MemoryInfo>,
+    pub interfaces: BTreeMap<String, InterfaceBytes>,
+}
+
+/// Blocks on reading /proc, which is quick but not async.
+#[must_use]
+pub fn read_sample() -> Sample {
+    let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+
+    Sample {
+        taken_at: Instant::now(),
+        cores: parse_stat(&read("/proc/stat")),
+        memory: system::parse_meminfo(&read("/proc/meminfo")),
+        interfaces: parse_net_dev(&read("/proc/net/dev")),
+    }
+}
+
+/// The `cpuN` lines, leaving out the `cpu` line that adds them all up.
+#[must_use]
+pub fn parse_stat(stat: &str) -> Vec<CpuTimes> {
+    stat.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            name.strip_prefix("cpu")?.parse::<usize>().ok()?;
+
+            // user nice system idle iowait irq softirq steal, then guest times that
+            // are already counted in user and nice
+            let times: Vec<u64> = fields
+                .take(8)
+                .map(|field| field.parse::<u64>().ok())
+                .collect::<Option<_>>()?;
+            let idle = times.get(3)? + times.get(4).copied().unwrap_or_default();
+            let total: u64 = times.iter().sum();
+
+            Some(CpuTimes {
+                busy: total - idle,
+                total,
+            })
+        })
+        .collect()
+}
+
+/// Every interface but loopback, by name.
+#[must_use]
+pub fn parse_net_dev(net_dev: &str) -> BTreeMap<String, InterfaceBytes> {
+    net_dev
+        .lines()
+        // Two header lines
+        .skip(2)
+        .filter_map(|line| {
+            let (name, counters) = line.split_once(':')?;
+            let name = name.trim();
+            let counters: Vec<&str> = counters.split_whitespace().collect();
+
+            // Received bytes come first, sent bytes after the eight received counters
+            Some((
+                name.to_owned(),
+                InterfaceBytes {
+                    received: counters.first()?.parse().ok()?,
+                    sent: counters.get(8)?.parse().ok()?,
+                },
+            ))
+        })
+        .filter(|(name, _)| name != "lo")
+        .collect()
+}
+
+/// The share of the time between two readings a core spent busy, from 0 to 1.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn cpu_usage(previous: CpuTimes, current: CpuTimes) -> f32 {
+    let total = current.total.saturating_sub(previous.total);
+    let busy = current.busy.saturating_sub(previous.busy);
+
+    if total == 0 {
+        0.0
+    } else {
+        (busy as f64 / total as f64).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// The share of `total` that isn't `free`, from 0 to 1.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn used_share(free: u64, total: u64) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (total.saturating_sub(free) as f64 / total as f64) as f32
+    }
+}
+
+/// Bytes a second received and sent between two readings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Throughput {
+    pub received: f64,
+    pub sent: f64,
+}
+
+/// Rolling graphs of the last [`HISTORY_LENGTH`] samples, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    previous: Option<Sample>,
+    pub cores: Vec<VecDeque<f32>>,
+    pub memory: VecDeque<f32>,
+    pub swap: VecDeque<f32>,
+    pub interfaces: BTreeMap<String, VecDeque<Throughput>>,
+}
+
+impl History {
+    /// Usage needs two readings, so the first sample only starts the history.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn push(&mut self, sample: Sample) {
+        if let Some(memory) = sample.memory {
+            push_rolling(
+                &mut self.memory,
+                used_share(memory.available_bytes, memory.total_bytes),
+            );
+            push_rolling(
+                &mut self.swap,
+                used_share(memory.swap_free_bytes, memory.swap_total_bytes),
+            );
+        }
+
+        if let Some(previous) = self.previous.take() {
+            let elapsed = sample
+                .taken_at
+                .saturating_duration_since(previous.taken_at)
+                .as_secs_f64();
+
+            // Cores come and go when they're taken offline
+            self.cores.resize_with(sample.cores.len(), VecDeque::new);
+            for ((history, previous), current) in self
+                .cores
+                .iter_mut()
+                .zip(&previous.cores)
+                .zip(&sample.cores)
+            {
+                push_rolling(history, cpu_usage(*previous, *current));
+            }
+
+            self.interfaces
+                .retain(|name, _| sample.interfaces.contains_key(name));
+            for (name, current) in &sample.interfaces {
+                let Some(previous) = previous.interfaces.get(name) else {
+                    continue;
+                };
+                let throughput = if elapsed > 0.0 {
+                    Throughput {
+                        received: current.received.saturating_sub(previous.received) as f64
+                            / elapsed,
+                        sent: current.sent.saturating_sub(previous.sent) as f64 / elapsed,
+                    }
+                } else {
+                    Throughput::default()
+                };
+
+                push_rolling(self.interfaces.entry(name.clone()).or_default(), throughput);
+            }
+        }
+
+        self.previous = Some(sample);
+    }
+}
+
+fn push_rolling<T>(history: &mut VecDeque<T>, value: T) {
+    if history.len() == HISTORY_LENGTH {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
+    use super::{
+        cpu_usage, parse_net_dev, parse_stat, used_share, CpuTimes, History, InterfaceBytes,
+        Sample, HISTORY_LENGTH,
+    };
+
+    #[test]
+    fn parses_per_core_times() {
+        let cores = parse_stat(include_str!("../../../resources/fixtures/proc/stat"));
+
+        assert_eq!(cores.len(), 4);
+        assert_eq!(
+            cores[0],
+            CpuTimes {
+                busy: 8_000,
+                total: 100_000
+            }
+        );
+        assert!((cpu_usage(cores[0], cores[1]) - 0.5).abs() < f32::EPSILON);
+        assert!((used_share(25, 100) - 0.75).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn parses_interfaces_but_loopback() {
+        let interfaces = parse_net_dev(include_str!("../../../resources/fixtures/proc/net/dev"));
+
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["enp3s0", "wlp2s0"]);
+        assert_eq!(
+            interfaces["wlp2s0"],
+            InterfaceBytes {
+                received: 1_523_881_122,
+                sent: 98_211_532,
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_a_rolling_history() {
+        let start = std::time::Instant::now();
+        let sample = |seconds: u64, busy: u64, received: u64| Sample {
+            taken_at: start + Duration::from_secs(seconds),
+            cores: vec![CpuTimes {
+                busy,
+                total: seconds * 100,
+            }],
+            memory: None,
+            interfaces: BTreeMap::from([("eth0".to_owned(), InterfaceBytes { received, sent: 0 })]),
+        };
+        let mut history = History::default();
+
+        history.push(sample(0, 0, 0));
+        assert!(history.cores.is_empty());
+
+        for second in 1..=100 {
+            history.push(sample(second, second * 25, second * 2048));
+        }
+
+        assert_eq!(history.cores[0].len(), HISTORY_LENGTH);
+        assert!((history.cores[0][0] - 0.25).abs() < f32::EPSILON);
+        assert!((history.interfaces["eth0"][0].received - 2048.0).abs() < f64::EPSILON);
+    }
+}
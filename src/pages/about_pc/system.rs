@@ -0,0 +1,341 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Mounts of these file systems are read-only images, such as snaps, whose usage is
+/// always full.
+const IMAGE_FS_TYPES: [&str; 2] = ["squashfs", "erofs"];
+/// Where /proc/cpuinfo has the CPU's model, by preference. On ARM there's only the
+/// board's or the system on a chip's.
+const CPU_MODEL_KEYS: [&str; 4] = ["model name", "cpu model", "Model", "Hardware"];
+
+/// What the About PC page lists about the machine, each part `None` when it couldn't
+/// be read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemOverview {
+    pub kernel: Option<String>,
+    pub hostname: Option<String>,
+    /// The vendor and product name from the firmware, such as `LENOVO 21CB`.
+    pub machine: Option<String>,
+    pub uptime: Option<Duration>,
+    pub cpu: Option<CpuInfo>,
+    pub memory: Option<MemoryInfo>,
+    pub mounts: Vec<MountUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuInfo {
+    pub model: String,
+    pub physical_cores: usize,
+    pub logical_cores: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryInfo {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub swap_total_bytes: u64,
+    pub swap_free_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub device: String,
+    pub mount_point: PathBuf,
+    pub fs_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountUsage {
+    pub mount: Mount,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Reads everything from /proc and /sys, which blocks on slow mounts.
+#[must_use]
+pub fn read_overview() -> SystemOverview {
+    let read = |path: &str| std::fs::read_to_string(path).ok();
+
+    SystemOverview {
+        kernel: read("/proc/sys/kernel/osrelease").and_then(|it| non_empty(&it)),
+        hostname: read("/proc/sys/kernel/hostname").and_then(|it| non_empty(&it)),
+        machine: parse_machine(
+            read("/sys/class/dmi/id/sys_vendor").as_deref(),
+            read("/sys/class/dmi/id/product_name").as_deref(),
+        ),
+        uptime: read("/proc/uptime").and_then(|it| parse_uptime(&it)),
+        cpu: read("/proc/cpuinfo").and_then(|it| parse_cpuinfo(&it)),
+        memory: read("/proc/meminfo").and_then(|it| parse_meminfo(&it)),
+        mounts: read("/proc/mounts")
+            .map(|it| parse_mounts(&it))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(mount_usage)
+            .collect(),
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    Some(value.trim())
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+/// Firmware often has placeholders such as `To Be Filled By O.E.M.` in its fields,
+/// which are left out.
+#[must_use]
+pub fn parse_machine(vendor: Option<&str>, product_name: Option<&str>) -> Option<String> {
+    let is_placeholder = |value: &String| {
+        let value = value.to_ascii_lowercase();
+
+        [
+            "o.e.m.",
+            "default string",
+            "system product name",
+            "system manufacturer",
+        ]
+        .iter()
+        .any(|placeholder| value.contains(placeholder))
+    };
+    let parts: Vec<String> = [vendor, product_name]
+        .into_iter()
+        .filter_map(|part| part.and_then(non_empty))
+        .filter(|part| !is_placeholder(part))
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// The first of the two numbers in /proc/uptime, seconds since boot.
+#[must_use]
+pub fn parse_uptime(uptime: &str) -> Option<Duration> {
+    let seconds = uptime.split_whitespace().next()?.parse::<f64>().ok()?;
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// /proc/cpuinfo lists every logical core.
+#[must_use]
+pub fn parse_cpuinfo(cpuinfo: &str) -> Option<CpuInfo> {
+    let fields = || {
+        cpuinfo.lines().filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+
+            Some((key.trim(), value.trim()))
+        })
+    };
+    let model = CPU_MODEL_KEYS.iter().find_map(|model_key| {
+        fields().find_map(|(key, value)| (key == *model_key).then(|| non_empty(value))?)
+    });
+    let mut logical_cores = 0;
+    let mut physical_core_ids = HashSet::new();
+    let mut physical_id = None;
+
+    for (key, value) in fields() {
+        match key {
+            "processor" => {
+                logical_cores += 1;
+                physical_id = None;
+            }
+            "physical id" => physical_id = Some(value.to_owned()),
+            "core id" => {
+                physical_core_ids.insert((physical_id.clone(), value.to_owned()));
+            }
+            _ => {}
+        }
+    }
+
+    (logical_cores > 0).then(|| CpuInfo {
+        model: model.unwrap_or_default(),
+        // Without core ids, as on ARM, there's no telling which cores are siblings
+        physical_cores: if physical_core_ids.is_empty() {
+            logical_cores
+        } else {
+            physical_core_ids.len()
+        },
+        logical_cores,
+    })
+}
+
+/// /proc/meminfo's values are in KiB, whatever its `kB` says.
+#[must_use]
+pub fn parse_meminfo(meminfo: &str) -> Option<MemoryInfo> {
+    let value = |name: &str| {
+        meminfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let kibibytes = value.split_whitespace().next()?.parse::<u64>().ok()?;
+
+            (key == name).then_some(kibibytes.saturating_mul(1024))
+        })
+    };
+
+    Some(MemoryInfo {
+        total_bytes: value("MemTotal")?,
+        // MemAvailable is missing before Linux 3.14
+        available_bytes: value("MemAvailable").or_else(|| value("MemFree"))?,
+        swap_total_bytes: value("SwapTotal").unwrap_or_default(),
+        swap_free_bytes: value("SwapFree").unwrap_or_default(),
+    })
+}
+
+/// The mounts of block devices, leaving out virtual file systems, images and a
+/// device's other mounts, such as btrfs subvolumes.
+#[must_use]
+pub fn parse_mounts(mounts: &str) -> Vec<Mount> {
+    let mut devices = HashSet::new();
+
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+
+            Some(Mount {
+                device: unescape_mount_field(fields.next()?),
+                mount_point: PathBuf::from(unescape_mount_field(fields.next()?)),
+                fs_type: fields.next()?.to_owned(),
+            })
+        })
+        .filter(|mount| {
+            mount.device.starts_with("/dev/")
+                && !IMAGE_FS_TYPES.contains(&mount.fs_type.as_str())
+                && devices.insert(mount.device.clone())
+        })
+        .collect()
+}
+
+/// Spaces, tabs, newlines and backslashes are written as octal escapes, such as
+/// `\040`.
+fn unescape_mount_field(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+
+    while let Some(index) = rest.find('\\') {
+        unescaped.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if let Some(byte) = rest
+            .get(1..4)
+            .and_then(|octal| u8::from_str_radix(octal, 8).ok())
+        {
+            unescaped.push(char::from(byte));
+            rest = &rest[4..];
+        } else {
+            unescaped.push('\\');
+            rest = &rest[1..];
+        }
+    }
+    unescaped.push_str(rest);
+
+    unescaped
+}
+
+fn mount_usage(mount: Mount) -> Option<MountUsage> {
+    let stats = rustix::fs::statvfs(Path::new(&mount.mount_point)).ok()?;
+
+    Some(MountUsage {
+        total_bytes: stats.f_blocks.saturating_mul(stats.f_frsize),
+        available_bytes: stats.f_bavail.saturating_mul(stats.f_frsize),
+        mount,
+    })
+}
+
+/// `1.5 GiB`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{
+        format_bytes, parse_cpuinfo, parse_machine, parse_meminfo, parse_mounts, parse_uptime,
+    };
+
+    #[test]
+    fn parses_x86_cpuinfo() {
+        let cpu = parse_cpuinfo(include_str!("../../../resources/fixtures/proc/cpuinfo")).unwrap();
+
+        assert_eq!(cpu.model, "AMD Ryzen 7 5800U with Radeon Graphics");
+        assert_eq!(cpu.logical_cores, 4);
+        assert_eq!(cpu.physical_cores, 2);
+    }
+
+    #[test]
+    fn parses_arm_cpuinfo() {
+        let cpu =
+            parse_cpuinfo(include_str!("../../../resources/fixtures/proc/cpuinfo_arm")).unwrap();
+
+        assert_eq!(cpu.model, "Raspberry Pi 4 Model B Rev 1.4");
+        assert_eq!(cpu.logical_cores, 4);
+        assert_eq!(cpu.physical_cores, 4);
+        assert_eq!(parse_cpuinfo(""), None);
+    }
+
+    #[test]
+    fn parses_meminfo() {
+        let memory =
+            parse_meminfo(include_str!("../../../resources/fixtures/proc/meminfo")).unwrap();
+
+        assert_eq!(memory.total_bytes, 16_069_232 * 1024);
+        assert_eq!(memory.available_bytes, 9_843_612 * 1024);
+        assert_eq!(memory.swap_total_bytes, 8_388_604 * 1024);
+        assert_eq!(memory.swap_free_bytes, 8_126_460 * 1024);
+        assert_eq!(parse_meminfo("MemFree: 12 kB"), None);
+    }
+
+    #[test]
+    fn parses_mounts() {
+        let mounts = parse_mounts(include_str!("../../../resources/fixtures/proc/mounts"));
+        let mount_points: Vec<PathBuf> =
+            mounts.into_iter().map(|mount| mount.mount_point).collect();
+
+        assert_eq!(
+            mount_points,
+            [
+                PathBuf::from("/"),
+                PathBuf::from("/boot/efi"),
+                PathBuf::from("/media/user/USB Drive"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_uptime_and_machine() {
+        assert_eq!(
+            parse_uptime(include_str!("../../../resources/fixtures/proc/uptime")),
+            Some(Duration::from_millis(93_784_250))
+        );
+        assert_eq!(parse_uptime("-1 0"), None);
+        assert_eq!(
+            parse_machine(Some("LENOVO\n"), Some("21CB\n")),
+            Some("LENOVO 21CB".to_owned())
+        );
+        assert_eq!(parse_machine(Some("To Be Filled By O.E.M.\n"), None), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(16_069_232 * 1024), "15.3 GiB");
+    }
+}
@@ -1,43 +1,1863 @@
-use chrono::{DateTime, Utc};
+mod alarms;
+mod analog;
+mod calendar;
+mod converter;
+mod format;
+mod notification;
+mod pomodoro;
+mod sntp;
+mod stopwatch;
+mod sun;
+mod timers;
+mod timestamp;
+mod world_clock;
+
+pub use alarms::Alarm;
+pub use analog::ClockFace;
+pub use calendar::Holiday;
+pub use format::ClockFormat;
+pub use pomodoro::PomodoroSettings;
+pub use sun::Coordinates;
+pub use timers::CountdownTimer;
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
 use cosmic::{
-    iced::{Length, Subscription},
-    widget, Element, Task,
+    cosmic_config, cosmic_theme,
+    iced::{
+        alignment::{Horizontal, Vertical},
+        Length, Subscription,
+    },
+    theme,
+    widget::{self, icon, segmented_button},
+    Apply, Element, Task,
 };
 use futures_util::SinkExt as _;
-use tokio_stream::StreamExt as _;
 
-use crate::{app, fl, locale::get_locale, pages};
+use crate::{
+    app, config, fl,
+    locale::{get_first_weekday, get_locale},
+    pages,
+};
+use analog::AnalogClock;
+use converter::Zone;
+use format::HourCycle;
+use pomodoro::{FocusSession, FocusStats, Phase, Pomodoro, PomodoroSetting};
+use sntp::SntpSample;
+use stopwatch::Stopwatch;
+use sun::{Crossing, SunDay};
+use timers::TimerState;
+use timestamp::{Representation, TimestampFormat};
+use world_clock::ZoneTime;
 
 #[derive(Default)]
 pub struct ClockPage {
+    config: config::UniConfig,
+    config_handler: Option<cosmic_config::Config>,
     pub system_time: Option<DateTime<Utc>>,
+    zone_query: String,
+    editing_custom_pattern: String,
+    clock_face_options: Vec<String>,
+    tab_model: segmented_button::SingleSelectModel,
+    stopwatch: Stopwatch,
+    /// When the stopwatch was last redrawn.
+    stopwatch_now: Option<Instant>,
+    /// Where the laps were last exported to, or why they couldn't be.
+    stopwatch_export_status: Option<String>,
+    timer_name: String,
+    timer_duration: String,
+    timer_play_sound: bool,
+    alarm_label: String,
+    alarm_time: String,
+    alarm_weekdays: Vec<Weekday>,
+    alarm_snooze_minutes: String,
+    alarm_error: Option<String>,
+    pomodoro: Pomodoro,
+    /// What's typed into each of [`PomodoroSetting::ALL`]'s inputs.
+    pomodoro_setting_inputs: [String; 4],
+    /// `None` until the session log is read.
+    focus_sessions: Option<Vec<FocusSession>>,
+    /// Sessions finished before the log was read, added to it once it is.
+    unloaded_focus_sessions: Vec<FocusSession>,
+    focus_sessions_error: Option<String>,
+    editing_ntp_warning_threshold: String,
+    ntp_checking: bool,
+    ntp_sample: Option<Result<SntpSample, String>>,
+    converter_date: String,
+    converter_time: String,
+    /// The zone the converter's date and time are read in.
+    converter_source: Zone,
+    converter_zone_options: Vec<String>,
+    timestamp_input: String,
+    /// The first day of the month the calendar shows.
+    calendar_month: NaiveDate,
+    calendar_first_weekday_options: Vec<String>,
+    calendar_import_status: Option<String>,
+    editing_sun_latitude: String,
+    editing_sun_longitude: String,
+    /// Worked out again when the local date or the coordinates change.
+    sun_day: Option<(NaiveDate, SunDay)>,
+}
+
+enum ClockPageTabs {
+    Clock,
+    Stopwatch,
+    Timers,
+    Alarms,
+    Pomodoro,
+    Converter,
+    Timestamp,
 }
 
 #[derive(Debug, Clone)]
 pub enum ClockPageMessage {
     SystemTimeTick(DateTime<Utc>),
+    AnalogClockFrame,
+    ClockFaceSelected(usize),
+    ZoneQueryInput(String),
+    ZoneAdd(String),
+    ZoneRemove(usize),
+    ZoneMoveUp(usize),
+    ZoneMoveDown(usize),
+    HourCycleToggled(bool),
+    ShowDateToggled(bool),
+    CustomPatternInput(String),
+    SwitchTab(segmented_button::Entity),
+    StopwatchTick(Instant),
+    StopwatchStart,
+    StopwatchPause,
+    StopwatchReset,
+    StopwatchLap,
+    StopwatchLapsCopy,
+    StopwatchLapsExport,
+    StopwatchLapsExported(Result<PathBuf, String>),
+    TimerNameInput(String),
+    TimerDurationInput(String),
+    TimerPlaySoundToggled(bool),
+    TimerAdd,
+    TimerStart(usize),
+    TimerPause(usize),
+    TimerReset(usize),
+    TimerRemove(usize),
+    TimerSoundToggled(usize, bool),
+    AlarmLabelInput(String),
+    AlarmTimeInput(String),
+    AlarmWeekdayToggled(Weekday),
+    AlarmSnoozeMinutesInput(String),
+    AlarmAdd,
+    AlarmToggled(usize, bool),
+    AlarmSnooze(usize),
+    AlarmDismiss(usize),
+    AlarmRemove(usize),
+    PomodoroStart,
+    PomodoroPause,
+    PomodoroSkip,
+    PomodoroReset,
+    PomodoroSettingInput(PomodoroSetting, String),
+    FocusSessionsLoaded(Result<Vec<FocusSession>, String>),
+    FocusSessionLogged(Result<(), String>),
+    NtpServerInput(String),
+    NtpWarningThresholdInput(String),
+    NtpCheck,
+    NtpChecked(Result<SntpSample, String>),
+    ConverterDateInput(String),
+    ConverterTimeInput(String),
+    ConverterSourceSelected(usize),
+    ConverterNow,
+    TimestampInput(String),
+    TimestampNow,
+    TimestampCopy(String),
+    CalendarPreviousMonth,
+    CalendarNextMonth,
+    CalendarToday,
+    CalendarFirstWeekdaySelected(usize),
+    CalendarImportHolidays,
+    CalendarHolidaysImported(Result<Vec<Holiday>, String>),
+    CalendarClearHolidays,
+    SunLatitudeInput(String),
+    SunLongitudeInput(String),
+    Notified(Result<(), String>),
 }
 
+const CALENDAR_CELL_WIDTH: f32 = 40.0;
+
 impl From<ClockPageMessage> for app::UniAppMessage {
     fn from(message: ClockPageMessage) -> Self {
         pages::Message::Clock(message).into()
     }
 }
 
-#[allow(clippy::unused_self)]
-impl pages::IPage<ClockPageMessage> for ClockPage {
-    fn view(&self) -> Element<ClockPageMessage> {
-        widget::container(
-            widget::column().push(
-                widget::text::title1(if let Some(system_time) = self.system_time {
-                    system_time.format_localized("%T", get_locale()).to_string()
+impl ClockPage {
+    #[must_use]
+    pub fn new(config: config::UniConfig, config_handler: Option<cosmic_config::Config>) -> Self {
+        Self {
+            editing_custom_pattern: config
+                .clock_format
+                .custom_pattern
+                .clone()
+                .unwrap_or_default(),
+            editing_ntp_warning_threshold: config
+                .ntp_warning_threshold_ms
+                .map(|threshold_ms| threshold_ms.to_string())
+                .unwrap_or_default(),
+            converter_zone_options: converter_zone_options(&config.world_clock_zones),
+            converter_date: chrono::Local::now()
+                .format(converter::DATE_INPUT_PATTERN)
+                .to_string(),
+            converter_time: chrono::Local::now()
+                .format(converter::TIME_INPUT_PATTERN)
+                .to_string(),
+            pomodoro_setting_inputs: PomodoroSetting::ALL
+                .map(|setting| setting.get(&config.pomodoro_settings).to_string()),
+            editing_sun_latitude: config
+                .sun_coordinates
+                .map(|coordinates| coordinates.latitude().to_string())
+                .unwrap_or_default(),
+            editing_sun_longitude: config
+                .sun_coordinates
+                .map(|coordinates| coordinates.longitude().to_string())
+                .unwrap_or_default(),
+            config,
+            config_handler,
+            calendar_month: calendar::month_of(chrono::Local::now().date_naive()),
+            calendar_first_weekday_options: std::iter::once(fl!("calendar-first-weekday-locale"))
+                .chain(alarms::WEEKDAYS.into_iter().map(weekday_name))
+                .collect(),
+            clock_face_options: ClockFace::ALL.into_iter().map(clock_face_label).collect(),
+            timer_play_sound: true,
+            alarm_snooze_minutes: alarms::DEFAULT_SNOOZE_MINUTES.to_string(),
+            tab_model: segmented_button::SingleSelectModel::builder()
+                .insert(|it| {
+                    it.text(fl!("tab-clock"))
+                        .data(ClockPageTabs::Clock)
+                        .activate()
+                })
+                .insert(|it| it.text(fl!("tab-stopwatch")).data(ClockPageTabs::Stopwatch))
+                .insert(|it| it.text(fl!("tab-timers")).data(ClockPageTabs::Timers))
+                .insert(|it| it.text(fl!("tab-alarms")).data(ClockPageTabs::Alarms))
+                .insert(|it| it.text(fl!("tab-pomodoro")).data(ClockPageTabs::Pomodoro))
+                .insert(|it| it.text(fl!("tab-converter")).data(ClockPageTabs::Converter))
+                .insert(|it| it.text(fl!("tab-timestamp")).data(ClockPageTabs::Timestamp))
+                .build(),
+            ..Default::default()
+        }
+    }
+
+    fn view_stopwatch(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let elapsed = self
+            .stopwatch
+            .elapsed(self.stopwatch_now.unwrap_or_else(Instant::now));
+        let laps = self.stopwatch.laps();
+
+        let controls = widget::row()
+            .spacing(space_xxs)
+            .push(if self.stopwatch.is_running() {
+                widget::button::standard(fl!("stopwatch-pause"))
+                    .on_press(ClockPageMessage::StopwatchPause)
+            } else {
+                widget::button::suggested(fl!("stopwatch-start"))
+                    .on_press(ClockPageMessage::StopwatchStart)
+            })
+            .push(
+                widget::button::standard(fl!("stopwatch-lap")).apply(|widget| {
+                    if self.stopwatch.is_running() {
+                        widget.on_press(ClockPageMessage::StopwatchLap)
+                    } else {
+                        widget
+                    }
+                }),
+            )
+            .push(
+                widget::button::standard(fl!("stopwatch-reset"))
+                    .on_press(ClockPageMessage::StopwatchReset),
+            );
+
+        let lap_list = laps.iter().fold(widget::list_column(), |list, lap| {
+            list.add(
+                widget::row()
+                    .push(
+                        widget::text(fl!("stopwatch-lap-number", number = lap.number))
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::text::monotext(stopwatch::format_duration(lap.lap_time))
+                            .width(Length::Fill),
+                    )
+                    .push(widget::text::monotext(stopwatch::format_duration(
+                        lap.split_time,
+                    ))),
+            )
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .align_x(Horizontal::Center)
+            .push(
+                widget::text::title1(stopwatch::format_duration(elapsed))
+                    .font(cosmic::font::mono()),
+            )
+            .push(controls)
+            .push_maybe((!laps.is_empty()).then(|| {
+                widget::column()
+                    .spacing(space_xxs)
+                    .push(
+                        widget::row()
+                            .spacing(space_xxs)
+                            .push(
+                                widget::button::standard(fl!("stopwatch-laps-copy"))
+                                    .on_press(ClockPageMessage::StopwatchLapsCopy),
+                            )
+                            .push(
+                                widget::button::standard(fl!("stopwatch-laps-export"))
+                                    .on_press(ClockPageMessage::StopwatchLapsExport),
+                            ),
+                    )
+                    .push(
+                        widget::row()
+                            .push(widget::text::heading(fl!("stopwatch-lap")).width(Length::Fill))
+                            .push(
+                                widget::text::heading(fl!("stopwatch-lap-time"))
+                                    .width(Length::Fill),
+                            )
+                            .push(widget::text::heading(fl!("stopwatch-split-time"))),
+                    )
+                    .push(lap_list)
+            }))
+            .push_maybe(
+                self.stopwatch_export_status
+                    .as_deref()
+                    .map(widget::text::caption),
+            )
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// What's still needed while another page is showing: ticks every second for
+    /// running timers and a running Pomodoro, a wake-up per alarm otherwise, and
+    /// nothing once no alarm is enabled.
+    pub fn background_subscription(&self) -> Subscription<ClockPageMessage> {
+        let needs_ticks = self
+            .config
+            .countdown_timers
+            .iter()
+            .any(CountdownTimer::is_running)
+            || self.pomodoro.is_running();
+
+        if needs_ticks {
+            system_time_tick()
+        } else if self.config.alarms.iter().any(|alarm| alarm.enabled) {
+            alarm_tick(
+                self.config
+                    .alarms
+                    .iter()
+                    .filter(|alarm| alarm.enabled && alarm.validate().is_ok())
+                    .filter_map(|alarm| alarm.next_ring)
+                    .min(),
+            )
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn set_countdown_timers(&mut self, countdown_timers: Vec<CountdownTimer>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_countdown_timers(config_handler, countdown_timers)
+            {
+                tracing::error!("Error set_countdown_timers: {e}");
+            }
+        }
+    }
+
+    fn update_countdown_timer(&mut self, index: usize, update: impl FnOnce(&mut CountdownTimer)) {
+        let mut countdown_timers = self.config.countdown_timers.clone();
+
+        if let Some(countdown_timer) = countdown_timers.get_mut(index) {
+            update(countdown_timer);
+            self.set_countdown_timers(countdown_timers);
+        }
+    }
+
+    /// Finishes the timers that ran out, including those that did while the app was
+    /// closed, notifying for each.
+    fn finish_due_timers(&mut self, now: DateTime<Utc>) -> Task<ClockPageMessage> {
+        let mut countdown_timers = self.config.countdown_timers.clone();
+        let finished: Vec<CountdownTimer> = countdown_timers
+            .iter_mut()
+            .filter_map(|countdown_timer| {
+                countdown_timer
+                    .finish_if_due(now)
+                    .then(|| countdown_timer.clone())
+            })
+            .collect();
+
+        if finished.is_empty() {
+            return Task::none();
+        }
+
+        self.set_countdown_timers(countdown_timers);
+
+        Task::batch(finished.into_iter().map(|countdown_timer| {
+            Task::future(async move {
+                ClockPageMessage::Notified(
+                    notification::notify(
+                        fl!("timer-finished"),
+                        countdown_timer.name,
+                        countdown_timer.play_sound,
+                    )
+                    .await,
+                )
+            })
+        }))
+    }
+
+    fn view_countdown_timer(
+        &self,
+        index: usize,
+        countdown_timer: &CountdownTimer,
+    ) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let remaining_secs =
+            countdown_timer.remaining_secs(self.system_time.unwrap_or_else(Utc::now));
+
+        widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::column()
+                    .push(widget::text::heading(countdown_timer.name.as_str()))
+                    .push(widget::text::caption(
+                        if countdown_timer.state == TimerState::Finished {
+                            fl!("timer-done")
+                        } else {
+                            fl!(
+                                "timer-of",
+                                duration = timers::format_remaining(countdown_timer.duration_secs)
+                            )
+                        },
+                    ))
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::text::title4(timers::format_remaining(remaining_secs))
+                    .font(cosmic::font::mono()),
+            )
+            .push(
+                widget::toggler(countdown_timer.play_sound)
+                    .label(fl!("timer-sound"))
+                    .on_toggle(move |play_sound| {
+                        ClockPageMessage::TimerSoundToggled(index, play_sound)
+                    }),
+            )
+            .push(if countdown_timer.is_running() {
+                widget::button::icon(icon::from_name("media-playback-pause-symbolic"))
+                    .on_press(ClockPageMessage::TimerPause(index))
+            } else {
+                widget::button::icon(icon::from_name("media-playback-start-symbolic"))
+                    .on_press(ClockPageMessage::TimerStart(index))
+            })
+            .push(
+                widget::button::icon(icon::from_name("view-refresh-symbolic"))
+                    .on_press(ClockPageMessage::TimerReset(index)),
+            )
+            .push(
+                widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                    .on_press(ClockPageMessage::TimerRemove(index)),
+            )
+            .into()
+    }
+
+    fn view_timers(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let duration_secs = timers::parse_duration(&self.timer_duration);
+
+        let timer_list = self.config.countdown_timers.iter().enumerate().fold(
+            widget::list_column(),
+            |list, (index, countdown_timer)| {
+                list.add(self.view_countdown_timer(index, countdown_timer))
+            },
+        );
+
+        let add_form = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input(fl!("timer-name-placeholder"), &self.timer_name)
+                    .label(fl!("timer-name"))
+                    .on_input(ClockPageMessage::TimerNameInput),
+            )
+            .push(
+                widget::text_input("5:00", &self.timer_duration)
+                    .label(fl!("timer-duration"))
+                    .on_input(ClockPageMessage::TimerDurationInput),
+            )
+            .push(
+                widget::toggler(self.timer_play_sound)
+                    .label(fl!("timer-sound"))
+                    .on_toggle(ClockPageMessage::TimerPlaySoundToggled),
+            )
+            .push(widget::button::suggested(fl!("timer-add")).apply(|widget| {
+                if duration_secs.is_some() {
+                    widget.on_press(ClockPageMessage::TimerAdd)
+                } else {
+                    widget
+                }
+            }));
+
+        widget::column()
+            .spacing(space_s)
+            .push_maybe((!self.config.countdown_timers.is_empty()).then_some(timer_list))
+            .push(add_form)
+            .push_maybe(
+                (!self.timer_duration.is_empty() && duration_secs.is_none())
+                    .then(|| widget::text::caption(fl!("timer-duration-invalid"))),
+            )
+            .into()
+    }
+
+    fn set_alarms(&mut self, alarms: Vec<Alarm>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self.config.set_alarms(config_handler, alarms) {
+                tracing::error!("Error set_alarms: {e}");
+            }
+        }
+    }
+
+    fn update_alarm(&mut self, index: usize, update: impl FnOnce(&mut Alarm)) {
+        let mut alarms = self.config.alarms.clone();
+
+        if let Some(alarm) = alarms.get_mut(index) {
+            update(alarm);
+            self.set_alarms(alarms);
+        }
+    }
+
+    /// Rings the alarms that are due, skipping those the config file was hand-edited
+    /// into an invalid state.
+    fn ring_due_alarms(&mut self, now: DateTime<Utc>) -> Task<ClockPageMessage> {
+        let local_now = now.with_timezone(&chrono::Local);
+        let mut alarms = self.config.alarms.clone();
+        let rung: Vec<Alarm> = alarms
+            .iter_mut()
+            .filter(|alarm| alarm.is_due(now) && alarm.validate().is_ok())
+            .map(|alarm| {
+                alarm.ring(&local_now);
+                alarm.clone()
+            })
+            .collect();
+
+        if rung.is_empty() {
+            return Task::none();
+        }
+
+        self.set_alarms(alarms);
+
+        Task::batch(rung.into_iter().map(|alarm| {
+            let time = localized_time(alarm.time, self.config.clock_format.short_time_pattern());
+            let summary = if alarm.label.is_empty() {
+                fl!("alarm")
+            } else {
+                alarm.label
+            };
+
+            Task::future(async move {
+                ClockPageMessage::Notified(notification::notify(summary, time, true).await)
+            })
+        }))
+    }
+
+    fn view_alarm(&self, index: usize, alarm: &Alarm) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let clock_format = &self.config.clock_format;
+
+        let recurrence = match alarm.weekdays.len() {
+            0 => fl!("alarm-once"),
+            7 => fl!("alarm-every-day"),
+            _ => alarms::WEEKDAYS
+                .into_iter()
+                .filter(|weekday| alarm.weekdays.contains(weekday))
+                .map(weekday_name)
+                .collect::<Vec<String>>()
+                .join(", "),
+        };
+        let status = match (alarm.validate(), alarm.snoozed, alarm.next_ring) {
+            (Err(e), _, _) => e.to_string(),
+            (Ok(()), true, Some(next_ring)) => fl!(
+                "alarm-snoozed-until",
+                time = next_ring
+                    .with_timezone(&chrono::Local)
+                    .format_localized(clock_format.short_time_pattern(), get_locale())
+                    .to_string()
+            ),
+            _ => recurrence,
+        };
+
+        widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::text::title4(localized_time(
+                    alarm.time,
+                    clock_format.short_time_pattern(),
+                ))
+                .font(cosmic::font::mono()),
+            )
+            .push(
+                widget::column()
+                    .push(widget::text::heading(if alarm.label.is_empty() {
+                        fl!("alarm")
+                    } else {
+                        alarm.label.clone()
+                    }))
+                    .push(widget::text::caption(status))
+                    .width(Length::Fill),
+            )
+            .push_maybe(alarm.ringing.then(|| {
+                widget::button::suggested(fl!("alarm-snooze"))
+                    .on_press(ClockPageMessage::AlarmSnooze(index))
+            }))
+            .push_maybe((alarm.ringing || alarm.snoozed).then(|| {
+                widget::button::standard(fl!("alarm-dismiss"))
+                    .on_press(ClockPageMessage::AlarmDismiss(index))
+            }))
+            .push(
+                widget::toggler(alarm.enabled)
+                    .on_toggle(move |enabled| ClockPageMessage::AlarmToggled(index, enabled)),
+            )
+            .push(
+                widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                    .on_press(ClockPageMessage::AlarmRemove(index)),
+            )
+            .into()
+    }
+
+    fn view_alarms(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let alarm_list = self
+            .config
+            .alarms
+            .iter()
+            .enumerate()
+            .fold(widget::list_column(), |list, (index, alarm)| {
+                list.add(self.view_alarm(index, alarm))
+            });
+
+        let weekday_buttons =
+            alarms::WEEKDAYS
+                .into_iter()
+                .fold(widget::row().spacing(space_xxs), |row, weekday| {
+                    let name = weekday_name(weekday);
+
+                    row.push(
+                        if self.alarm_weekdays.contains(&weekday) {
+                            widget::button::suggested(name)
+                        } else {
+                            widget::button::standard(name)
+                        }
+                        .on_press(ClockPageMessage::AlarmWeekdayToggled(weekday)),
+                    )
+                });
+
+        let add_form = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input("07:00", &self.alarm_time)
+                    .label(fl!("alarm-time"))
+                    .on_input(ClockPageMessage::AlarmTimeInput),
+            )
+            .push(
+                widget::text_input(fl!("alarm"), &self.alarm_label)
+                    .label(fl!("alarm-label"))
+                    .on_input(ClockPageMessage::AlarmLabelInput),
+            )
+            .push(
+                widget::text_input(
+                    alarms::DEFAULT_SNOOZE_MINUTES.to_string(),
+                    &self.alarm_snooze_minutes,
+                )
+                .label(fl!("alarm-snooze-minutes"))
+                .on_input(ClockPageMessage::AlarmSnoozeMinutesInput),
+            )
+            .push(widget::button::suggested(fl!("alarm-add")).on_press(ClockPageMessage::AlarmAdd));
+
+        widget::column()
+            .spacing(space_s)
+            .push_maybe((!self.config.alarms.is_empty()).then_some(alarm_list))
+            .push(add_form)
+            .push(weekday_buttons)
+            .push_maybe(self.alarm_error.as_deref().map(widget::text::caption))
+            .into()
+    }
+
+    fn set_pomodoro_settings(&mut self, pomodoro_settings: PomodoroSettings) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_pomodoro_settings(config_handler, pomodoro_settings)
+            {
+                tracing::error!("Error set_pomodoro_settings: {e}");
+            }
+        }
+    }
+
+    /// Starts the next Pomodoro phase once the current one runs out, logging finished
+    /// work phases.
+    fn advance_pomodoro(&mut self, now: DateTime<Utc>) -> Task<ClockPageMessage> {
+        let Some(transition) = self
+            .pomodoro
+            .advance_if_due(now, &self.config.pomodoro_settings)
+        else {
+            return Task::none();
+        };
+
+        let summary = match transition.finished {
+            Phase::Work => fl!("pomodoro-work-over"),
+            Phase::ShortBreak | Phase::LongBreak => fl!("pomodoro-break-over"),
+        };
+        let body = fl!(
+            "pomodoro-next",
+            phase = phase_label(transition.next),
+            minutes = transition.next.minutes(&self.config.pomodoro_settings)
+        );
+        let notify_task = Task::future(async move {
+            ClockPageMessage::Notified(notification::notify(summary, body, true).await)
+        });
+
+        let log_task = match (transition.session, pomodoro::session_log_path()) {
+            (Some(session), Some(log_path)) => {
+                match self.focus_sessions.as_mut() {
+                    Some(focus_sessions) => focus_sessions.push(session.clone()),
+                    None => self.unloaded_focus_sessions.push(session.clone()),
+                }
+
+                Task::future(async move {
+                    ClockPageMessage::FocusSessionLogged(
+                        pomodoro::append_session(log_path, session).await,
+                    )
+                })
+            }
+            _ => Task::none(),
+        };
+
+        Task::batch([notify_task, log_task])
+    }
+
+    fn view_pomodoro(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let settings = &self.config.pomodoro_settings;
+        let remaining_secs = self
+            .pomodoro
+            .remaining_secs(self.system_time.unwrap_or_else(Utc::now), settings);
+
+        let controls = widget::row()
+            .spacing(space_xxs)
+            .push(if self.pomodoro.is_running() {
+                widget::button::standard(fl!("pomodoro-pause"))
+                    .on_press(ClockPageMessage::PomodoroPause)
+            } else {
+                widget::button::suggested(fl!("pomodoro-start"))
+                    .on_press(ClockPageMessage::PomodoroStart)
+            })
+            .push(
+                widget::button::standard(fl!("pomodoro-skip"))
+                    .on_press(ClockPageMessage::PomodoroSkip),
+            )
+            .push(
+                widget::button::standard(fl!("pomodoro-reset"))
+                    .on_press(ClockPageMessage::PomodoroReset),
+            );
+
+        let setting_inputs = PomodoroSetting::ALL
+            .into_iter()
+            .zip(&self.pomodoro_setting_inputs)
+            .fold(widget::row().spacing(space_xxs), |row, (setting, input)| {
+                row.push(
+                    widget::text_input(setting.get(settings).to_string(), input)
+                        .label(pomodoro_setting_label(setting))
+                        .on_input(move |input| {
+                            ClockPageMessage::PomodoroSettingInput(setting, input)
+                        }),
+                )
+            });
+        let has_invalid_setting = PomodoroSetting::ALL
+            .into_iter()
+            .zip(&self.pomodoro_setting_inputs)
+            .any(|(setting, input)| setting.parse(settings, input).is_none());
+
+        let stats = self.focus_sessions.as_ref().map(|focus_sessions| {
+            let stats = FocusStats::new(
+                focus_sessions,
+                &self
+                    .system_time
+                    .unwrap_or_else(Utc::now)
+                    .with_timezone(&chrono::Local),
+            );
+
+            widget::column()
+                .spacing(space_xxs)
+                .push(widget::text::title4(fl!("pomodoro-stats")))
+                .push(widget::text(fl!(
+                    "pomodoro-today",
+                    sessions = stats.today.sessions,
+                    minutes = stats.today.minutes
+                )))
+                .push(widget::text(fl!(
+                    "pomodoro-this-week",
+                    sessions = stats.this_week.sessions,
+                    minutes = stats.this_week.minutes
+                )))
+                .push(stats.days.into_iter().fold(
+                    widget::row().spacing(space_s),
+                    |row, (date, totals)| {
+                        row.push(
+                            widget::column()
+                                .align_x(Horizontal::Center)
+                                .push(widget::text::caption(weekday_name(date.weekday())))
+                                .push(widget::text::monotext(totals.minutes.to_string())),
+                        )
+                    },
+                ))
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .align_x(Horizontal::Center)
+            .push(widget::text::title3(phase_label(self.pomodoro.phase)))
+            .push(
+                widget::text::title1(timers::format_remaining(remaining_secs))
+                    .font(cosmic::font::mono()),
+            )
+            .push(widget::text::caption(fl!(
+                "pomodoro-session",
+                number = (self.pomodoro.completed_in_cycle + 1).min(settings.sessions_per_cycle),
+                total = settings.sessions_per_cycle
+            )))
+            .push(controls)
+            .push(setting_inputs)
+            .push_maybe(
+                has_invalid_setting.then(|| widget::text::caption(fl!("pomodoro-setting-invalid"))),
+            )
+            .push_maybe(stats)
+            .push_maybe(
+                self.focus_sessions_error
+                    .as_deref()
+                    .map(widget::text::caption),
+            )
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn set_ntp_server(&mut self, ntp_server: Option<String>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self.config.set_ntp_server(config_handler, ntp_server) {
+                tracing::error!("Error set_ntp_server: {e}");
+            }
+        }
+    }
+
+    fn set_ntp_warning_threshold_ms(&mut self, ntp_warning_threshold_ms: Option<u32>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_ntp_warning_threshold_ms(config_handler, ntp_warning_threshold_ms)
+            {
+                tracing::error!("Error set_ntp_warning_threshold_ms: {e}");
+            }
+        }
+    }
+
+    fn view_clock_drift(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let warning_threshold_ms = self
+            .config
+            .ntp_warning_threshold_ms
+            .unwrap_or(sntp::DEFAULT_WARNING_THRESHOLD_MS);
+
+        let settings = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input(
+                    sntp::DEFAULT_SERVER,
+                    self.config.ntp_server.as_deref().unwrap_or_default(),
+                )
+                .label(fl!("ntp-server"))
+                .on_input(ClockPageMessage::NtpServerInput),
+            )
+            .push(
+                widget::text_input(
+                    sntp::DEFAULT_WARNING_THRESHOLD_MS.to_string(),
+                    &self.editing_ntp_warning_threshold,
+                )
+                .label(fl!("ntp-warning-threshold"))
+                .on_input(ClockPageMessage::NtpWarningThresholdInput),
+            )
+            .push(
+                widget::button::standard(if self.ntp_checking {
+                    fl!("ntp-checking")
+                } else {
+                    fl!("ntp-check")
+                })
+                .apply(|widget| {
+                    if self.ntp_checking {
+                        widget
+                    } else {
+                        widget.on_press(ClockPageMessage::NtpCheck)
+                    }
+                }),
+            );
+
+        let result = self.ntp_sample.as_ref().map(|ntp_sample| match ntp_sample {
+            Ok(sample) => widget::column()
+                .push(widget::text(fl!(
+                    "ntp-result",
+                    offset = sntp::format_millis(sample.offset),
+                    delay = sntp::format_millis(sample.round_trip_delay),
+                    stratum = sample.stratum
+                )))
+                .push_maybe(sample.exceeds(warning_threshold_ms).then(|| {
+                    widget::text::heading(fl!(
+                        "ntp-offset-warning",
+                        threshold = warning_threshold_ms
+                    ))
+                })),
+            Err(e) => widget::column().push(widget::text(fl!("ntp-error", reason = e.as_str()))),
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .push(widget::text::title3(fl!("ntp-clock-drift")))
+            .push(settings)
+            .push_maybe(
+                (!self.editing_ntp_warning_threshold.is_empty()
+                    && self.editing_ntp_warning_threshold.parse::<u32>().is_err())
+                .then(|| widget::text::caption(fl!("ntp-warning-threshold-invalid"))),
+            )
+            .push_maybe(result)
+            .into()
+    }
+
+    fn set_clock_format(&mut self, clock_format: ClockFormat) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self.config.set_clock_format(config_handler, clock_format) {
+                tracing::error!("Error set_clock_format: {e}");
+            }
+        }
+    }
+
+    fn view_local_time(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let clock_format = &self.config.clock_format;
+        let local_time = self
+            .system_time
+            .map(|system_time| system_time.with_timezone(&chrono::Local));
+
+        let settings = widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::toggler(clock_format.hour_cycle == HourCycle::H24)
+                    .on_toggle(ClockPageMessage::HourCycleToggled),
+            )
+            .push(widget::text(fl!("clock-24-hour")))
+            .push(
+                widget::toggler(clock_format.show_date)
+                    .on_toggle(ClockPageMessage::ShowDateToggled),
+            )
+            .push(widget::text(fl!("clock-show-date")))
+            .push(widget::dropdown(
+                &self.clock_face_options,
+                ClockFace::ALL
+                    .iter()
+                    .position(|it| *it == self.config.clock_face),
+                ClockPageMessage::ClockFaceSelected,
+            ))
+            .push(
+                widget::text_input(format::DEFAULT_CUSTOM_PATTERN, &self.editing_custom_pattern)
+                    .label(fl!("clock-custom-pattern"))
+                    .on_input(ClockPageMessage::CustomPatternInput),
+            );
+
+        widget::column()
+            .spacing(space_xxs)
+            .align_x(Horizontal::Center)
+            .push_maybe(self.config.clock_face.shows_analog().then(|| {
+                // Square from the width it's given, as the page scrolls vertically
+                widget::container(
+                    widget::aspect_ratio::aspect_ratio_container(
+                        cosmic::iced::widget::canvas(AnalogClock {
+                            time: local_time
+                                .map_or(chrono::NaiveTime::MIN, |local_time| local_time.time()),
+                        })
+                        .width(Length::Fill)
+                        .height(Length::Fill),
+                        1.0,
+                    )
+                    .width(Length::Fill)
+                    .height(Length::Shrink),
+                )
+                .width(Length::Fill)
+                .max_width(analog::MAX_SIDE)
+            }))
+            .push_maybe(self.config.clock_face.shows_digital().then(|| {
+                widget::text::title1(if let Some(local_time) = local_time {
+                    local_time
+                        .format_localized(clock_format.time_pattern(), get_locale())
+                        .to_string()
                 } else {
                     fl!("system-time-na")
                 })
+                .font(cosmic::font::mono())
+            }))
+            .push_maybe(
+                local_time
+                    .filter(|_| clock_format.show_date)
+                    .map(|local_time| {
+                        widget::text::title4(
+                            local_time
+                                .format_localized(format::DATE_PATTERN, get_locale())
+                                .to_string(),
+                        )
+                    }),
+            )
+            .push(settings)
+            .push_maybe(
+                (!self.editing_custom_pattern.is_empty()
+                    && !format::is_valid_pattern(&self.editing_custom_pattern))
+                .then(|| widget::text::caption(fl!("clock-custom-pattern-invalid"))),
+            )
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn calendar_first_weekday(&self) -> Weekday {
+        self.config
+            .calendar_first_weekday
+            .unwrap_or_else(get_first_weekday)
+    }
+
+    fn view_calendar(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let first_weekday = self.calendar_first_weekday();
+        let today = self
+            .system_time
+            .map_or_else(chrono::Local::now, |system_time| {
+                system_time.with_timezone(&chrono::Local)
+            })
+            .date_naive();
+        let month = self.calendar_month;
+        let holidays = &self.config.calendar_holidays;
+        let cell = |content: Element<'static, ClockPageMessage>| {
+            widget::container(content).center_x(Length::Fixed(CALENDAR_CELL_WIDTH))
+        };
+
+        let header = widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::button::icon(icon::from_name("go-previous-symbolic"))
+                    .on_press(ClockPageMessage::CalendarPreviousMonth),
+            )
+            .push(
+                widget::text::title4(
+                    month
+                        .format_localized(calendar::MONTH_PATTERN, get_locale())
+                        .to_string(),
+                )
+                .width(Length::Fill)
+                .align_x(Horizontal::Center),
+            )
+            .push(
+                widget::button::icon(icon::from_name("go-next-symbolic"))
+                    .on_press(ClockPageMessage::CalendarNextMonth),
+            )
+            .push(
+                widget::button::standard(fl!("calendar-today"))
+                    .on_press(ClockPageMessage::CalendarToday),
+            );
+
+        let weekday_row = calendar::weekdays_from(first_weekday).into_iter().fold(
+            widget::row().push(cell(widget::text::caption(fl!("calendar-week")).into())),
+            |row, weekday| row.push(cell(widget::text::caption(weekday_name(weekday)).into())),
+        );
+
+        let weeks = calendar::month_weeks(month, first_weekday)
+            .into_iter()
+            .fold(widget::column().spacing(space_xxs), |weeks, week| {
+                let week_number = cell(widget::text::caption(week.iso_week.to_string()).into());
+
+                weeks.push(week.days.into_iter().fold(
+                    widget::row().align_y(Vertical::Center).push(week_number),
+                    |row, date| {
+                        let day = date.day().to_string();
+                        let day: Element<_> = if date == today {
+                            widget::button::suggested(day)
+                                .on_press(ClockPageMessage::CalendarToday)
+                                .into()
+                        } else if date.month() != month.month() {
+                            widget::text::caption(day).into()
+                        } else if holidays.iter().any(|holiday| holiday.falls_on(date)) {
+                            widget::text::heading(day).into()
+                        } else {
+                            widget::text::body(day).into()
+                        };
+
+                        row.push(cell(day))
+                    },
+                ))
+            });
+
+        let month_holidays = month
+            .iter_days()
+            .take_while(|date| date.month() == month.month())
+            .flat_map(|date| {
+                holidays
+                    .iter()
+                    .filter(move |holiday| holiday.falls_on(date))
+                    .map(move |holiday| (date, holiday))
+            })
+            .fold(widget::column(), |list, (date, holiday)| {
+                list.push(widget::text::caption(fl!(
+                    "calendar-holiday",
+                    day = date.day(),
+                    name = holiday.name.clone()
+                )))
+            });
+
+        let settings = widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(widget::text(fl!("calendar-first-weekday")))
+            .push(widget::dropdown(
+                &self.calendar_first_weekday_options,
+                Some(
+                    self.config
+                        .calendar_first_weekday
+                        .and_then(|weekday| alarms::WEEKDAYS.iter().position(|it| *it == weekday))
+                        .map_or(0, |index| index + 1),
+                ),
+                ClockPageMessage::CalendarFirstWeekdaySelected,
+            ))
+            .push(
+                widget::button::standard(fl!("calendar-import-holidays"))
+                    .on_press(ClockPageMessage::CalendarImportHolidays),
+            )
+            .push_maybe((!holidays.is_empty()).then(|| {
+                widget::button::destructive(fl!("calendar-clear-holidays"))
+                    .on_press(ClockPageMessage::CalendarClearHolidays)
+            }));
+
+        widget::column()
+            .spacing(space_s)
+            .push(header)
+            .push(weekday_row)
+            .push(weeks)
+            .push(month_holidays)
+            .push(settings)
+            .push_maybe(
+                self.calendar_import_status
+                    .as_ref()
+                    .map(|status| widget::text::caption(status.clone())),
+            )
+            // A week number column and seven days
+            .width(Length::Fixed(CALENDAR_CELL_WIDTH * 8.0))
+            .into()
+    }
+
+    fn set_calendar_first_weekday(&mut self, calendar_first_weekday: Option<Weekday>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_calendar_first_weekday(config_handler, calendar_first_weekday)
+            {
+                tracing::error!("Error set_calendar_first_weekday: {e}");
+            }
+        }
+    }
+
+    fn set_calendar_holidays(&mut self, calendar_holidays: Vec<Holiday>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_calendar_holidays(config_handler, calendar_holidays)
+            {
+                tracing::error!("Error set_calendar_holidays: {e}");
+            }
+        }
+    }
+
+    /// Only when the local date has changed since, unless `force`d.
+    fn update_sun_day(&mut self, force: bool) {
+        let local_date = self
+            .system_time
+            .map_or_else(chrono::Local::now, |system_time| {
+                system_time.with_timezone(&chrono::Local)
+            })
+            .date_naive();
+
+        if force || self.sun_day.is_none_or(|(date, _)| date != local_date) {
+            self.sun_day = self.config.sun_coordinates.and_then(|coordinates| {
+                sun::sun_day(local_date, coordinates).map(|sun_day| (local_date, sun_day))
+            });
+        }
+    }
+
+    fn set_sun_coordinates(&mut self) {
+        let sun_coordinates =
+            if self.editing_sun_latitude.is_empty() && self.editing_sun_longitude.is_empty() {
+                None
+            } else if let Some(coordinates) =
+                Coordinates::parse(&self.editing_sun_latitude, &self.editing_sun_longitude)
+            {
+                Some(coordinates)
+            } else {
+                // Invalid coordinates stay in the inputs only, until they're fixed
+                return;
+            };
+
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_sun_coordinates(config_handler, sun_coordinates)
+            {
+                tracing::error!("Error set_sun_coordinates: {e}");
+            }
+        }
+
+        self.update_sun_day(true);
+    }
+
+    fn view_sun(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let time_pattern = self.config.clock_format.short_time_pattern();
+        let local_time = |instant: DateTime<Utc>| {
+            instant
+                .with_timezone(&chrono::Local)
+                .format_localized(time_pattern, get_locale())
+                .to_string()
+        };
+        let crossing_times = |crossing: Crossing| match crossing {
+            Crossing::Times { rise, set } => (local_time(rise), local_time(set)),
+            Crossing::AlwaysAbove => (fl!("sun-always-above"), fl!("sun-always-above")),
+            Crossing::AlwaysBelow => (fl!("sun-always-below"), fl!("sun-always-below")),
+        };
+
+        let settings = widget::row()
+            .spacing(space_xxs)
+            .push(
+                widget::text_input("21.0285", &self.editing_sun_latitude)
+                    .label(fl!("sun-latitude"))
+                    .on_input(ClockPageMessage::SunLatitudeInput),
+            )
+            .push(
+                widget::text_input("105.8542", &self.editing_sun_longitude)
+                    .label(fl!("sun-longitude"))
+                    .on_input(ClockPageMessage::SunLongitudeInput),
+            );
+
+        let times = self.sun_day.map(|(_, sun_day)| {
+            let (sunrise, sunset) = crossing_times(sun_day.sunrise_sunset);
+            let (civil_dawn, civil_dusk) = crossing_times(sun_day.civil_twilight);
+            let day_length_minutes = sun_day.day_length().num_minutes();
+
+            [
+                (fl!("sun-civil-dawn"), civil_dawn),
+                (fl!("sun-sunrise"), sunrise),
+                (fl!("sun-solar-noon"), local_time(sun_day.solar_noon)),
+                (fl!("sun-sunset"), sunset),
+                (fl!("sun-civil-dusk"), civil_dusk),
+                (
+                    fl!("sun-day-length"),
+                    fl!(
+                        "sun-day-length-value",
+                        hours = day_length_minutes / 60,
+                        minutes = day_length_minutes % 60
+                    ),
+                ),
+            ]
+            .into_iter()
+            .fold(widget::list_column(), |list, (label, value)| {
+                list.add(
+                    widget::row()
+                        .push(widget::text(label).width(Length::Fill))
+                        .push(widget::text::monotext(value)),
+                )
+            })
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .push(widget::text::title3(fl!("sun")))
+            .push(settings)
+            .push_maybe(
+                (self.config.sun_coordinates.is_none()
+                    || Coordinates::parse(&self.editing_sun_latitude, &self.editing_sun_longitude)
+                        .is_none())
+                .then(|| widget::text::caption(fl!("sun-coordinates-hint"))),
+            )
+            .push_maybe(times)
+            .into()
+    }
+
+    fn set_world_clock_zones(&mut self, world_clock_zones: Vec<String>) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_world_clock_zones(config_handler, world_clock_zones)
+            {
+                tracing::error!("Error set_world_clock_zones: {e}");
+            }
+        }
+
+        if !self.converter_zones().contains(&self.converter_source) {
+            self.converter_source = None;
+        }
+        self.converter_zone_options = converter_zone_options(&self.config.world_clock_zones);
+    }
+
+    /// The system's zone, then the world clock's.
+    fn converter_zones(&self) -> Vec<Zone> {
+        std::iter::once(None)
+            .chain(
+                self.config
+                    .world_clock_zones
+                    .iter()
+                    .filter_map(|name| name.parse::<Tz>().ok())
+                    .map(Some),
+            )
+            .collect()
+    }
+
+    fn view_converted_time(
+        &self,
+        zone: Zone,
+        time: DateTime<chrono::FixedOffset>,
+        source_date: NaiveDate,
+    ) -> Element<ClockPageMessage> {
+        let time_pattern = format!(
+            "%a %e %b, {}",
+            self.config.clock_format.short_time_pattern()
+        );
+
+        widget::row()
+            .align_y(Vertical::Center)
+            .push(
+                widget::column()
+                    .push(widget::text::heading(zone_label(zone)))
+                    .push(widget::text::caption(fl!(
+                        "world-clock-zone",
+                        zone = zone.map_or_else(
+                            || fl!("converter-local-zone"),
+                            |tz| { tz.name().to_owned() }
+                        ),
+                        offset = world_clock::format_utc_offset(time.offset().local_minus_utc()),
+                        days = (time.date_naive() - source_date).num_days()
+                    )))
+                    .width(Length::Fill),
+            )
+            .push(widget::text::title4(
+                time.format_localized(&time_pattern, get_locale())
+                    .to_string(),
+            ))
+            .into()
+    }
+
+    fn view_converter(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let zones = self.converter_zones();
+        let input = converter::parse_input(&self.converter_date, &self.converter_time);
+        let instant = input.and_then(|naive| converter::resolve(self.converter_source, naive));
+
+        let inputs = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input("2025-01-31", &self.converter_date)
+                    .label(fl!("converter-date"))
+                    .on_input(ClockPageMessage::ConverterDateInput),
+            )
+            .push(
+                widget::text_input("09:30", &self.converter_time)
+                    .label(fl!("converter-time"))
+                    .on_input(ClockPageMessage::ConverterTimeInput),
+            )
+            .push(widget::dropdown(
+                &self.converter_zone_options,
+                zones.iter().position(|it| *it == self.converter_source),
+                ClockPageMessage::ConverterSourceSelected,
+            ))
+            .push(
+                widget::button::standard(fl!("converter-now"))
+                    .on_press(ClockPageMessage::ConverterNow),
+            );
+
+        let error = match (input, instant) {
+            (None, _) => Some(fl!("converter-input-invalid")),
+            (Some(_), None) => Some(fl!("converter-time-skipped")),
+            (Some(_), Some(_)) => None,
+        };
+
+        let converted = instant.map(|instant| {
+            zones.iter().fold(widget::list_column(), |list, zone| {
+                list.add(self.view_converted_time(
+                    *zone,
+                    converter::in_zone(instant, *zone),
+                    instant.date_naive(),
+                ))
+            })
+        });
+
+        // The source zone's day, side by side with the rest
+        let planner_zones: Vec<Zone> = std::iter::once(self.converter_source)
+            .chain(
+                zones
+                    .iter()
+                    .copied()
+                    .filter(|zone| *zone != self.converter_source),
+            )
+            .collect();
+        let planner = instant.map(|instant| {
+            let short_time_pattern = self.config.clock_format.short_time_pattern();
+            let cell_width = Length::FillPortion(1);
+            let header = planner_zones.iter().fold(
+                widget::row()
+                    .spacing(space_xxs)
+                    .push(widget::Space::with_width(Length::Fixed(24.0))),
+                |row, zone| row.push(widget::text::heading(zone_label(*zone)).width(cell_width)),
+            );
+
+            converter::planner_rows(instant.date_naive(), &planner_zones)
+                .into_iter()
+                .fold(widget::column().push(header), |column, planner_row| {
+                    let marker: Element<ClockPageMessage> = if planner_row.is_working_everywhere {
+                        icon::from_name("emblem-ok-symbolic").size(24).icon().into()
+                    } else {
+                        widget::Space::with_width(Length::Fixed(24.0)).into()
+                    };
+                    let row = widget::row()
+                        .spacing(space_xxs)
+                        .align_y(Vertical::Center)
+                        .push(marker);
+
+                    column.push(planner_row.times.iter().fold(row, |row, time| {
+                        let label = time
+                            .format_localized(short_time_pattern, get_locale())
+                            .to_string();
+
+                        row.push(if converter::is_working_hour(time) {
+                            widget::text::heading(label).width(cell_width)
+                        } else {
+                            widget::text::body(label).width(cell_width)
+                        })
+                    }))
+                })
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .push(inputs)
+            .push_maybe(error.map(widget::text::caption))
+            .push_maybe(converted)
+            .push_maybe(
+                planner
+                    .is_some()
+                    .then(|| widget::text::title4(fl!("converter-planner"))),
+            )
+            .push_maybe(planner)
+            .into()
+    }
+
+    fn view_timestamp(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let detected = timestamp::detect(&self.timestamp_input);
+
+        let input = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input("1700000000", &self.timestamp_input)
+                    .label(fl!("timestamp-input"))
+                    .on_input(ClockPageMessage::TimestampInput),
+            )
+            .push(
+                widget::button::standard(fl!("timestamp-now")).apply(|widget| {
+                    if self.system_time.is_some() {
+                        widget.on_press(ClockPageMessage::TimestampNow)
+                    } else {
+                        widget
+                    }
+                }),
+            );
+
+        let caption = match detected {
+            Some((timestamp_format, _)) => Some(fl!(
+                "timestamp-detected",
+                format = timestamp_format_label(timestamp_format)
+            )),
+            None if self.timestamp_input.trim().is_empty() => None,
+            None => Some(fl!("timestamp-unrecognized")),
+        };
+
+        let representations = detected.map(|(_, instant)| {
+            Representation::ALL
+                .into_iter()
+                .fold(widget::list_column(), |list, representation| {
+                    let value = representation.format(instant);
+
+                    list.add(
+                        widget::row()
+                            .align_y(Vertical::Center)
+                            .spacing(space_xxs)
+                            .push(
+                                widget::column()
+                                    .push(widget::text::caption(representation_label(
+                                        representation,
+                                    )))
+                                    .push(widget::text::monotext(
+                                        value.clone().unwrap_or_else(|| fl!("timestamp-na")),
+                                    ))
+                                    .width(Length::Fill),
+                            )
+                            .push(
+                                widget::button::icon(icon::from_name("edit-copy-symbolic")).apply(
+                                    |widget| {
+                                        if let Some(value) = value {
+                                            widget.on_press(ClockPageMessage::TimestampCopy(value))
+                                        } else {
+                                            widget
+                                        }
+                                    },
+                                ),
+                            ),
+                    )
+                })
+        });
+
+        widget::column()
+            .spacing(space_s)
+            .push(input)
+            .push_maybe(caption.map(widget::text::caption))
+            .push_maybe(representations)
+            .into()
+    }
+
+    fn view_zone(&self, index: usize, zone_time: ZoneTime) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+        let zone_count = self.config.world_clock_zones.len();
+
+        widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::column()
+                    .push(widget::text::heading(zone_time.city))
+                    .push(widget::text::caption(fl!(
+                        "world-clock-zone",
+                        zone = zone_time.tz.name(),
+                        offset = zone_time.utc_offset.as_str(),
+                        days = zone_time.day_difference
+                    )))
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::text::title4(
+                    zone_time
+                        .time
+                        .format_localized(
+                            self.config.clock_format.short_time_pattern(),
+                            get_locale(),
+                        )
+                        .to_string(),
+                )
                 .font(cosmic::font::mono()),
-            ),
+            )
+            .push(
+                widget::button::icon(icon::from_name("go-up-symbolic")).apply(|widget| {
+                    if index > 0 {
+                        widget.on_press(ClockPageMessage::ZoneMoveUp(index))
+                    } else {
+                        widget
+                    }
+                }),
+            )
+            .push(
+                widget::button::icon(icon::from_name("go-down-symbolic")).apply(|widget| {
+                    if index + 1 < zone_count {
+                        widget.on_press(ClockPageMessage::ZoneMoveDown(index))
+                    } else {
+                        widget
+                    }
+                }),
+            )
+            .push(
+                widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                    .on_press(ClockPageMessage::ZoneRemove(index)),
+            )
+            .into()
+    }
+
+    fn view_world_clock(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let zone_count = self.config.world_clock_zones.len();
+
+        let zones = self.system_time.map_or_else(Vec::new, |now| {
+            let local_date = now.with_timezone(&chrono::Local).date_naive();
+
+            self.config
+                .world_clock_zones
+                .iter()
+                .enumerate()
+                // Zones dropped from the tz database are kept, but can't be shown
+                .filter_map(|(index, name)| {
+                    Some((
+                        index,
+                        ZoneTime::new(name.parse::<Tz>().ok()?, now, local_date),
+                    ))
+                })
+                .collect()
+        });
+
+        let zone_list = zones
+            .into_iter()
+            .fold(widget::list_column(), |list, (index, zone_time)| {
+                list.add(self.view_zone(index, zone_time))
+            });
+
+        let suggestions = widget::flex_row(
+            world_clock::search_zones(&self.zone_query, &self.config.world_clock_zones)
+                .into_iter()
+                .map(|tz| {
+                    widget::button::standard(tz.name())
+                        .on_press(ClockPageMessage::ZoneAdd(tz.name().to_owned()))
+                        .into()
+                })
+                .collect(),
+        )
+        .row_spacing(space_xxs)
+        .column_spacing(space_xxs);
+
+        widget::column()
+            .spacing(space_s)
+            .push(widget::text::title3(fl!("world-clock")))
+            .push_maybe((zone_count > 0).then_some(zone_list))
+            .push(
+                widget::text_input(fl!("world-clock-search"), &self.zone_query)
+                    .label(fl!("world-clock-add"))
+                    .on_input(ClockPageMessage::ZoneQueryInput),
+            )
+            .push(suggestions)
+            .into()
+    }
+}
+
+/// Ticks on every wall-clock second, so the seconds shown change together with the
+/// system clock's. Shared by the page and background subscriptions, so switching
+/// between them keeps the same stream running.
+fn system_time_tick() -> Subscription<ClockPageMessage> {
+    struct SystemTimeTickSubscription;
+
+    Subscription::run_with_id(
+        std::any::TypeId::of::<SystemTimeTickSubscription>(),
+        cosmic::iced::stream::channel(
+            std::mem::size_of::<ClockPageMessage>(),
+            move |mut channel| async move {
+                loop {
+                    _ = channel
+                        .send(ClockPageMessage::SystemTimeTick(Utc::now()))
+                        .await;
+
+                    tokio::time::sleep(until_next_second(Utc::now())).await;
+                }
+            },
+        ),
+    )
+}
+
+/// Ticks when `next_ring` is due, and at least once a minute so that a suspend or a
+/// clock change can't hold an alarm back for longer. Restarts whenever the next ring
+/// changes.
+fn alarm_tick(next_ring: Option<DateTime<Utc>>) -> Subscription<ClockPageMessage> {
+    struct AlarmTickSubscription;
+
+    Subscription::run_with_id(
+        (std::any::TypeId::of::<AlarmTickSubscription>(), next_ring),
+        cosmic::iced::stream::channel(
+            std::mem::size_of::<ClockPageMessage>(),
+            move |mut channel| async move {
+                loop {
+                    tokio::time::sleep(until_next_alarm_tick(Utc::now(), next_ring)).await;
+
+                    _ = channel
+                        .send(ClockPageMessage::SystemTimeTick(Utc::now()))
+                        .await;
+                }
+            },
+        ),
+    )
+}
+
+fn until_next_alarm_tick(
+    now: DateTime<Utc>,
+    next_ring: Option<DateTime<Utc>>,
+) -> std::time::Duration {
+    let a_minute = TimeDelta::minutes(1);
+    // An alarm that stays due, when its ring couldn't be saved, mustn't spin the loop
+    let a_second = TimeDelta::seconds(1);
+
+    next_ring
+        .map_or(a_minute, |next_ring| {
+            (next_ring - now).clamp(a_second, a_minute)
+        })
+        .to_std()
+        .unwrap_or_default()
+}
+
+fn until_next_second(now: DateTime<Utc>) -> std::time::Duration {
+    // Leap seconds report 1_000_000_000 or more
+    let subsec_nanos = now.timestamp_subsec_nanos() % 1_000_000_000;
+
+    std::time::Duration::from_nanos(u64::from(1_000_000_000 - subsec_nanos))
+}
+
+fn converter_zone_options(world_clock_zones: &[String]) -> Vec<String> {
+    std::iter::once(fl!("converter-local-zone"))
+        .chain(
+            world_clock_zones
+                .iter()
+                .filter(|name| name.parse::<Tz>().is_ok())
+                .cloned(),
         )
-        .center(Length::Fill)
+        .collect()
+}
+
+fn zone_label(zone: Zone) -> String {
+    zone.map_or_else(|| fl!("converter-local-zone"), world_clock::city)
+}
+
+fn timestamp_format_label(timestamp_format: TimestampFormat) -> String {
+    match timestamp_format {
+        TimestampFormat::EpochSeconds => fl!("timestamp-epoch-seconds"),
+        TimestampFormat::EpochMillis => fl!("timestamp-epoch-millis"),
+        TimestampFormat::Rfc3339 => fl!("timestamp-rfc3339"),
+        TimestampFormat::Rfc2822 => fl!("timestamp-rfc2822"),
+    }
+}
+
+fn representation_label(representation: Representation) -> String {
+    match representation {
+        Representation::EpochSeconds => fl!("timestamp-epoch-seconds"),
+        Representation::EpochMillis => fl!("timestamp-epoch-millis"),
+        Representation::Rfc3339Utc => fl!("timestamp-rfc3339-utc"),
+        Representation::Rfc3339Local => fl!("timestamp-rfc3339-local"),
+        Representation::Rfc2822Utc => fl!("timestamp-rfc2822-utc"),
+        Representation::Rfc2822Local => fl!("timestamp-rfc2822-local"),
+    }
+}
+
+fn clock_face_label(clock_face: ClockFace) -> String {
+    match clock_face {
+        ClockFace::Analog => fl!("clock-face-analog"),
+        ClockFace::Digital => fl!("clock-face-digital"),
+        ClockFace::Both => fl!("clock-face-both"),
+    }
+}
+
+fn phase_label(phase: Phase) -> String {
+    match phase {
+        Phase::Work => fl!("pomodoro-work"),
+        Phase::ShortBreak => fl!("pomodoro-short-break"),
+        Phase::LongBreak => fl!("pomodoro-long-break"),
+    }
+}
+
+fn pomodoro_setting_label(setting: PomodoroSetting) -> String {
+    match setting {
+        PomodoroSetting::WorkMinutes => fl!("pomodoro-work-minutes"),
+        PomodoroSetting::ShortBreakMinutes => fl!("pomodoro-short-break-minutes"),
+        PomodoroSetting::LongBreakMinutes => fl!("pomodoro-long-break-minutes"),
+        PomodoroSetting::SessionsPerCycle => fl!("pomodoro-sessions-per-cycle"),
+    }
+}
+
+/// The locale's short name, taken from a date that falls on `weekday`.
+fn weekday_name(weekday: Weekday) -> String {
+    NaiveDate::from_isoywd_opt(2024, 1, weekday).map_or_else(
+        || weekday.to_string(),
+        |date| {
+            Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN))
+                .format_localized("%a", get_locale())
+                .to_string()
+        },
+    )
+}
+
+/// `time` with the locale's AM/PM markers, which chrono only applies to dates with a
+/// zone.
+fn localized_time(time: NaiveTime, pattern: &str) -> String {
+    Utc.from_utc_datetime(&NaiveDate::default().and_time(time))
+        .format_localized(pattern, get_locale())
+        .to_string()
+}
+
+impl pages::IPage<ClockPageMessage> for ClockPage {
+    fn view(&self) -> Element<ClockPageMessage> {
+        let cosmic_theme::Spacing { space_m, .. } = theme::active().cosmic().spacing;
+
+        widget::column::with_children(vec![
+            widget::tab_bar::horizontal(&self.tab_model)
+                .on_activate(ClockPageMessage::SwitchTab)
+                .width(Length::Fill)
+                .into(),
+            match self.tab_model.active_data::<ClockPageTabs>() {
+                Some(ClockPageTabs::Stopwatch) => self.view_stopwatch(),
+                Some(ClockPageTabs::Timers) => self.view_timers(),
+                Some(ClockPageTabs::Alarms) => self.view_alarms(),
+                Some(ClockPageTabs::Pomodoro) => self.view_pomodoro(),
+                Some(ClockPageTabs::Converter) => self.view_converter(),
+                Some(ClockPageTabs::Timestamp) => self.view_timestamp(),
+                Some(ClockPageTabs::Clock) | None => widget::column()
+                    .spacing(space_m)
+                    .push(
+                        widget::row()
+                            .spacing(space_m)
+                            .push(self.view_local_time())
+                            .push(self.view_calendar()),
+                    )
+                    .push(self.view_world_clock())
+                    .push(self.view_sun())
+                    .push(self.view_clock_drift())
+                    .into(),
+            },
+        ])
+        .spacing(space_m)
+        .apply(widget::scrollable)
         .into()
     }
 
@@ -45,32 +1865,497 @@ impl pages::IPage<ClockPageMessage> for ClockPage {
         match message {
             ClockPageMessage::SystemTimeTick(date_time) => {
                 self.system_time = Some(date_time);
+                self.update_sun_day(false);
+
+                return Task::batch([
+                    self.finish_due_timers(date_time),
+                    self.ring_due_alarms(date_time),
+                    self.advance_pomodoro(date_time),
+                ]);
+            }
+            ClockPageMessage::AnalogClockFrame => {
+                // Timers and alarms wait for the next tick, this only moves the hands
+                self.system_time = Some(Utc::now());
+            }
+            ClockPageMessage::ClockFaceSelected(index) => {
+                if let Some(clock_face) = ClockFace::ALL.get(index) {
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self.config.set_clock_face(config_handler, *clock_face) {
+                            tracing::error!("Error set_clock_face: {e}");
+                        }
+                    }
+                }
+            }
+            ClockPageMessage::SwitchTab(id) => {
+                self.tab_model.activate(id);
+            }
+            ClockPageMessage::StopwatchTick(now) => {
+                self.stopwatch_now = Some(now);
+            }
+            ClockPageMessage::StopwatchStart => {
+                let now = Instant::now();
+
+                self.stopwatch.start(now);
+                self.stopwatch_now = Some(now);
+            }
+            ClockPageMessage::StopwatchPause => {
+                let now = Instant::now();
+
+                self.stopwatch.pause(now);
+                self.stopwatch_now = Some(now);
+            }
+            ClockPageMessage::StopwatchReset => {
+                self.stopwatch.reset();
+                self.stopwatch_now = None;
+                self.stopwatch_export_status = None;
+            }
+            ClockPageMessage::StopwatchLap => {
+                self.stopwatch.lap(Instant::now());
+            }
+            ClockPageMessage::StopwatchLapsCopy => match self.stopwatch.laps_csv() {
+                Ok(laps_csv) => return cosmic::iced::clipboard::write(laps_csv),
+                Err(e) => tracing::error!("StopwatchLapsCopy failed: {e}"),
+            },
+            ClockPageMessage::StopwatchLapsExport => {
+                let laps_csv = match self.stopwatch.laps_csv() {
+                    Ok(laps_csv) => laps_csv,
+                    Err(e) => {
+                        tracing::error!("StopwatchLapsExport failed: {e}");
+                        return Task::none();
+                    }
+                };
+
+                if let Some(laps_path) = rfd::FileDialog::new()
+                    .set_title(fl!("stopwatch-laps-export-dialog-title"))
+                    .add_filter("csv", &["csv"])
+                    .set_file_name("laps.csv")
+                    .save_file()
+                {
+                    return Task::future(async move {
+                        ClockPageMessage::StopwatchLapsExported(
+                            tokio::fs::write(&laps_path, laps_csv)
+                                .await
+                                .map(|()| laps_path)
+                                .map_err(|e| e.to_string()),
+                        )
+                    });
+                }
+            }
+            ClockPageMessage::StopwatchLapsExported(exported) => {
+                self.stopwatch_export_status = Some(match exported {
+                    Ok(laps_path) => fl!(
+                        "stopwatch-laps-exported",
+                        path = laps_path.display().to_string()
+                    ),
+                    Err(e) => {
+                        tracing::error!("StopwatchLapsExported failed: {e}");
+                        fl!("stopwatch-laps-export-failed", reason = e)
+                    }
+                });
+            }
+            ClockPageMessage::TimerNameInput(timer_name) => {
+                self.timer_name = timer_name;
+            }
+            ClockPageMessage::TimerDurationInput(timer_duration) => {
+                self.timer_duration = timer_duration;
+            }
+            ClockPageMessage::TimerPlaySoundToggled(play_sound) => {
+                self.timer_play_sound = play_sound;
+            }
+            ClockPageMessage::TimerAdd => {
+                if let Some(duration_secs) = timers::parse_duration(&self.timer_duration) {
+                    let mut countdown_timers = self.config.countdown_timers.clone();
+                    let name = match self.timer_name.trim() {
+                        "" => fl!("timer-default-name", number = countdown_timers.len() + 1),
+                        name => name.to_owned(),
+                    };
+                    let mut countdown_timer =
+                        CountdownTimer::new(name, duration_secs, self.timer_play_sound);
+
+                    countdown_timer.start(Utc::now());
+                    countdown_timers.push(countdown_timer);
+
+                    self.set_countdown_timers(countdown_timers);
+                    self.timer_name.clear();
+                    self.timer_duration.clear();
+                }
+            }
+            ClockPageMessage::TimerStart(index) => {
+                self.update_countdown_timer(index, |countdown_timer| {
+                    countdown_timer.start(Utc::now());
+                });
+            }
+            ClockPageMessage::TimerPause(index) => {
+                self.update_countdown_timer(index, |countdown_timer| {
+                    countdown_timer.pause(Utc::now());
+                });
+            }
+            ClockPageMessage::TimerReset(index) => {
+                self.update_countdown_timer(index, CountdownTimer::reset);
+            }
+            ClockPageMessage::TimerSoundToggled(index, play_sound) => {
+                self.update_countdown_timer(index, |countdown_timer| {
+                    countdown_timer.play_sound = play_sound;
+                });
+            }
+            ClockPageMessage::TimerRemove(index) => {
+                if index < self.config.countdown_timers.len() {
+                    let mut countdown_timers = self.config.countdown_timers.clone();
+                    countdown_timers.remove(index);
+
+                    self.set_countdown_timers(countdown_timers);
+                }
+            }
+            ClockPageMessage::AlarmLabelInput(alarm_label) => {
+                self.alarm_label = alarm_label;
+            }
+            ClockPageMessage::AlarmTimeInput(alarm_time) => {
+                self.alarm_time = alarm_time;
+            }
+            ClockPageMessage::AlarmWeekdayToggled(weekday) => {
+                if let Some(index) = self.alarm_weekdays.iter().position(|it| *it == weekday) {
+                    self.alarm_weekdays.remove(index);
+                } else {
+                    self.alarm_weekdays.push(weekday);
+                }
+            }
+            ClockPageMessage::AlarmSnoozeMinutesInput(alarm_snooze_minutes) => {
+                self.alarm_snooze_minutes = alarm_snooze_minutes;
+            }
+            ClockPageMessage::AlarmAdd => {
+                let Ok(time) = NaiveTime::parse_from_str(self.alarm_time.trim(), "%H:%M") else {
+                    self.alarm_error = Some(fl!("alarm-time-invalid"));
+                    return Task::none();
+                };
+                let snooze_minutes = match self.alarm_snooze_minutes.trim() {
+                    "" => alarms::DEFAULT_SNOOZE_MINUTES,
+                    snooze_minutes => snooze_minutes.parse().unwrap_or_default(),
+                };
+
+                match Alarm::new(
+                    self.alarm_label.trim().to_owned(),
+                    time,
+                    self.alarm_weekdays.clone(),
+                    snooze_minutes,
+                ) {
+                    Ok(mut alarm) => {
+                        let mut alarms = self.config.alarms.clone();
+
+                        alarm.schedule(&chrono::Local::now());
+                        alarms.push(alarm);
+
+                        self.set_alarms(alarms);
+                        self.alarm_label.clear();
+                        self.alarm_time.clear();
+                        self.alarm_weekdays.clear();
+                        self.alarm_error = None;
+                    }
+                    Err(e) => {
+                        self.alarm_error = Some(e.to_string());
+                    }
+                }
+            }
+            ClockPageMessage::AlarmToggled(index, enabled) => {
+                self.update_alarm(index, |alarm| {
+                    if enabled {
+                        alarm.schedule(&chrono::Local::now());
+                    } else {
+                        alarm.disable();
+                    }
+                });
+            }
+            ClockPageMessage::AlarmSnooze(index) => {
+                self.update_alarm(index, |alarm| alarm.snooze(Utc::now()));
+            }
+            ClockPageMessage::AlarmDismiss(index) => {
+                self.update_alarm(index, |alarm| alarm.dismiss(&chrono::Local::now()));
+            }
+            ClockPageMessage::AlarmRemove(index) => {
+                if index < self.config.alarms.len() {
+                    let mut alarms = self.config.alarms.clone();
+                    alarms.remove(index);
+
+                    self.set_alarms(alarms);
+                }
+            }
+            ClockPageMessage::PomodoroStart => {
+                self.pomodoro
+                    .start(Utc::now(), &self.config.pomodoro_settings);
+            }
+            ClockPageMessage::PomodoroPause => {
+                self.pomodoro
+                    .pause(Utc::now(), &self.config.pomodoro_settings);
+            }
+            ClockPageMessage::PomodoroSkip => {
+                self.pomodoro
+                    .skip(Utc::now(), &self.config.pomodoro_settings);
+            }
+            ClockPageMessage::PomodoroReset => {
+                self.pomodoro.reset();
+            }
+            ClockPageMessage::PomodoroSettingInput(setting, input) => {
+                // Out of range values stay in the input only, until they're fixed
+                if let Some(pomodoro_settings) =
+                    setting.parse(&self.config.pomodoro_settings, &input)
+                {
+                    self.set_pomodoro_settings(pomodoro_settings);
+                }
+
+                if let Some(index) = PomodoroSetting::ALL.iter().position(|it| *it == setting) {
+                    self.pomodoro_setting_inputs[index] = input;
+                }
+            }
+            ClockPageMessage::FocusSessionsLoaded(focus_sessions) => match focus_sessions {
+                Ok(mut focus_sessions) => {
+                    // The read may or may not have seen their appends
+                    for session in std::mem::take(&mut self.unloaded_focus_sessions) {
+                        if !focus_sessions.contains(&session) {
+                            focus_sessions.push(session);
+                        }
+                    }
+
+                    self.focus_sessions = Some(focus_sessions);
+                    self.focus_sessions_error = None;
+                }
+                Err(e) => {
+                    tracing::error!("FocusSessionsLoaded failed: {e}");
+                    self.focus_sessions_error = Some(fl!("pomodoro-log-error", reason = e));
+                }
+            },
+            ClockPageMessage::FocusSessionLogged(logged) => {
+                if let Err(e) = logged {
+                    tracing::error!("FocusSessionLogged failed: {e}");
+                    self.focus_sessions_error = Some(fl!("pomodoro-log-error", reason = e));
+                }
+            }
+            ClockPageMessage::NtpServerInput(ntp_server) => {
+                self.set_ntp_server((!ntp_server.trim().is_empty()).then_some(ntp_server));
+            }
+            ClockPageMessage::NtpWarningThresholdInput(threshold) => {
+                // Non-numbers stay in the input only, until they're fixed
+                if threshold.is_empty() {
+                    self.set_ntp_warning_threshold_ms(None);
+                } else if let Ok(threshold_ms) = threshold.parse::<u32>() {
+                    self.set_ntp_warning_threshold_ms(Some(threshold_ms));
+                }
+
+                self.editing_ntp_warning_threshold = threshold;
+            }
+            ClockPageMessage::NtpCheck => {
+                let ntp_server = self
+                    .config
+                    .ntp_server
+                    .clone()
+                    .unwrap_or_else(|| sntp::DEFAULT_SERVER.to_owned());
+                self.ntp_checking = true;
+
+                return Task::future(async move {
+                    ClockPageMessage::NtpChecked(
+                        sntp::query(&ntp_server).await.map_err(|e| e.to_string()),
+                    )
+                });
+            }
+            ClockPageMessage::NtpChecked(ntp_sample) => {
+                if let Err(e) = &ntp_sample {
+                    tracing::error!("NtpChecked failed: {e}");
+                }
+
+                self.ntp_checking = false;
+                self.ntp_sample = Some(ntp_sample);
+            }
+            ClockPageMessage::ConverterDateInput(converter_date) => {
+                self.converter_date = converter_date;
+            }
+            ClockPageMessage::ConverterTimeInput(converter_time) => {
+                self.converter_time = converter_time;
+            }
+            ClockPageMessage::ConverterSourceSelected(index) => {
+                if let Some(zone) = self.converter_zones().get(index) {
+                    self.converter_source = *zone;
+                }
+            }
+            ClockPageMessage::ConverterNow => {
+                let now = converter::in_zone(Utc::now().fixed_offset(), self.converter_source);
+
+                self.converter_date = now.format(converter::DATE_INPUT_PATTERN).to_string();
+                self.converter_time = now.format(converter::TIME_INPUT_PATTERN).to_string();
+            }
+            ClockPageMessage::TimestampInput(timestamp_input) => {
+                self.timestamp_input = timestamp_input;
+            }
+            ClockPageMessage::TimestampNow => {
+                if let Some(system_time) = self.system_time {
+                    self.timestamp_input = system_time.timestamp().to_string();
+                }
+            }
+            ClockPageMessage::TimestampCopy(value) => {
+                return cosmic::iced::clipboard::write(value);
+            }
+            ClockPageMessage::CalendarPreviousMonth => {
+                self.calendar_month = calendar::add_months(self.calendar_month, -1);
+            }
+            ClockPageMessage::CalendarNextMonth => {
+                self.calendar_month = calendar::add_months(self.calendar_month, 1);
+            }
+            ClockPageMessage::CalendarToday => {
+                self.calendar_month = calendar::month_of(chrono::Local::now().date_naive());
+            }
+            ClockPageMessage::CalendarFirstWeekdaySelected(index) => {
+                // The first option follows the locale
+                self.set_calendar_first_weekday(
+                    index
+                        .checked_sub(1)
+                        .and_then(|index| alarms::WEEKDAYS.get(index).copied()),
+                );
+            }
+            ClockPageMessage::CalendarImportHolidays => {
+                if let Some(ics_path) = rfd::FileDialog::new()
+                    .set_title(fl!("calendar-import-dialog-title"))
+                    .add_filter("ics", &["ics"])
+                    .pick_file()
+                {
+                    return Task::future(async move {
+                        ClockPageMessage::CalendarHolidaysImported(
+                            match tokio::fs::read(&ics_path).await {
+                                Ok(ics) => calendar::parse_holidays(ics.as_slice()),
+                                Err(e) => Err(e.to_string()),
+                            },
+                        )
+                    });
+                }
+            }
+            ClockPageMessage::CalendarHolidaysImported(imported) => match imported {
+                Ok(imported) => {
+                    self.calendar_import_status =
+                        Some(fl!("calendar-holidays-imported", count = imported.len()));
+
+                    let mut holidays = self.config.calendar_holidays.clone();
+                    holidays.extend(imported);
+                    holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+                    holidays.dedup();
+                    self.set_calendar_holidays(holidays);
+                }
+                Err(e) => {
+                    tracing::error!("CalendarHolidaysImported failed: {e}");
+                    self.calendar_import_status = Some(fl!("calendar-import-failed", reason = e));
+                }
+            },
+            ClockPageMessage::CalendarClearHolidays => {
+                self.calendar_import_status = None;
+                self.set_calendar_holidays(vec![]);
+            }
+            ClockPageMessage::SunLatitudeInput(latitude) => {
+                self.editing_sun_latitude = latitude;
+                self.set_sun_coordinates();
+            }
+            ClockPageMessage::SunLongitudeInput(longitude) => {
+                self.editing_sun_longitude = longitude;
+                self.set_sun_coordinates();
+            }
+            ClockPageMessage::Notified(notified) => {
+                if let Err(e) = notified {
+                    tracing::error!("Notified failed: {e}");
+                }
+            }
+            ClockPageMessage::HourCycleToggled(is_24_hour) => {
+                self.set_clock_format(ClockFormat {
+                    hour_cycle: if is_24_hour {
+                        HourCycle::H24
+                    } else {
+                        HourCycle::H12
+                    },
+                    ..self.config.clock_format.clone()
+                });
+            }
+            ClockPageMessage::ShowDateToggled(show_date) => {
+                self.set_clock_format(ClockFormat {
+                    show_date,
+                    ..self.config.clock_format.clone()
+                });
+            }
+            ClockPageMessage::CustomPatternInput(custom_pattern) => {
+                // Invalid patterns stay in the input only, until they're fixed
+                if custom_pattern.is_empty() {
+                    self.set_clock_format(ClockFormat {
+                        custom_pattern: None,
+                        ..self.config.clock_format.clone()
+                    });
+                } else if format::is_valid_pattern(&custom_pattern) {
+                    self.set_clock_format(ClockFormat {
+                        custom_pattern: Some(custom_pattern.clone()),
+                        ..self.config.clock_format.clone()
+                    });
+                }
+
+                self.editing_custom_pattern = custom_pattern;
+            }
+            ClockPageMessage::ZoneQueryInput(zone_query) => {
+                self.zone_query = zone_query;
+            }
+            ClockPageMessage::ZoneAdd(name) => {
+                if !self.config.world_clock_zones.contains(&name) {
+                    let mut world_clock_zones = self.config.world_clock_zones.clone();
+                    world_clock_zones.push(name);
+
+                    self.set_world_clock_zones(world_clock_zones);
+                    self.zone_query.clear();
+                }
+            }
+            ClockPageMessage::ZoneRemove(index) => {
+                if index < self.config.world_clock_zones.len() {
+                    let mut world_clock_zones = self.config.world_clock_zones.clone();
+                    world_clock_zones.remove(index);
+
+                    self.set_world_clock_zones(world_clock_zones);
+                }
+            }
+            ClockPageMessage::ZoneMoveUp(index) => {
+                if (1..self.config.world_clock_zones.len()).contains(&index) {
+                    let mut world_clock_zones = self.config.world_clock_zones.clone();
+                    world_clock_zones.swap(index - 1, index);
+
+                    self.set_world_clock_zones(world_clock_zones);
+                }
+            }
+            ClockPageMessage::ZoneMoveDown(index) => {
+                if index + 1 < self.config.world_clock_zones.len() {
+                    let mut world_clock_zones = self.config.world_clock_zones.clone();
+                    world_clock_zones.swap(index, index + 1);
+
+                    self.set_world_clock_zones(world_clock_zones);
+                }
             }
         }
 
         Task::none()
     }
 
+    fn on_init(&self) -> Task<ClockPageMessage> {
+        match (&self.focus_sessions, pomodoro::session_log_path()) {
+            (None, Some(log_path)) => Task::future(async move {
+                ClockPageMessage::FocusSessionsLoaded(pomodoro::load_sessions(log_path).await)
+            }),
+            _ => Task::none(),
+        }
+    }
+
     fn subscription(&self) -> Subscription<ClockPageMessage> {
-        struct SystemTimeTickSubscription;
-
-        Subscription::run_with_id(
-            std::any::TypeId::of::<SystemTimeTickSubscription>(),
-            cosmic::iced::stream::channel(
-                std::mem::size_of::<ClockPageMessage>(),
-                move |mut channel| async move {
-                    let interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
-                    let mut stream = tokio_stream::wrappers::IntervalStream::new(interval);
-
-                    while stream.next().await.is_some() {
-                        let system_time = chrono::Utc::now();
-
-                        _ = channel
-                            .send(ClockPageMessage::SystemTimeTick(system_time))
-                            .await;
-                    }
-                },
-            ),
-        )
+        let stopwatch_tick = if self.stopwatch.is_running() {
+            cosmic::iced::time::every(stopwatch::TICK).map(ClockPageMessage::StopwatchTick)
+        } else {
+            Subscription::none()
+        };
+
+        let analog_clock_frames = if self.config.clock_face.shows_analog()
+            && matches!(
+                self.tab_model.active_data::<ClockPageTabs>(),
+                Some(ClockPageTabs::Clock)
+            ) {
+            cosmic::iced::time::every(analog::FRAME).map(|_| ClockPageMessage::AnalogClockFrame)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([system_time_tick(), stopwatch_tick, analog_clock_frames])
     }
 }
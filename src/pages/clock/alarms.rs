@@ -0,0 +1,371 @@
+use std::fmt;
+
+use chrono::{
+    DateTime, Datelike, LocalResult, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::fl;
+
+pub const DEFAULT_SNOOZE_MINUTES: u32 = 10;
+pub const MAX_SNOOZE_MINUTES: u32 = 60;
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+/// Monday first, as ISO 8601 has it.
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// An alarm, persisted in `UniConfig`. Times are local wall-clock times, the next
+/// ring is kept in UTC so it's checked with a plain comparison on every tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alarm {
+    pub label: String,
+    pub time: NaiveTime,
+    /// The days it repeats on. It rings once when there are none.
+    pub weekdays: Vec<Weekday>,
+    pub snooze_minutes: u32,
+    pub enabled: bool,
+    /// `None` while disabled.
+    pub next_ring: Option<DateTime<Utc>>,
+    /// Rang and neither snoozed nor dismissed since.
+    pub ringing: bool,
+    pub snoozed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmError {
+    LabelTooLong,
+    DuplicateWeekday(Weekday),
+    SnoozeOutOfRange,
+}
+
+impl fmt::Display for AlarmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlarmError::LabelTooLong => {
+                write!(f, "{}", fl!("alarm-label-too-long", max = MAX_LABEL_LENGTH))
+            }
+            AlarmError::DuplicateWeekday(weekday) => write!(
+                f,
+                "{}",
+                fl!("alarm-duplicate-weekday", weekday = weekday.to_string())
+            ),
+            AlarmError::SnoozeOutOfRange => write!(
+                f,
+                "{}",
+                fl!("alarm-snooze-out-of-range", max = MAX_SNOOZE_MINUTES)
+            ),
+        }
+    }
+}
+
+impl Alarm {
+    /// A disabled alarm, validated but not scheduled yet.
+    pub fn new(
+        label: String,
+        time: NaiveTime,
+        weekdays: Vec<Weekday>,
+        snooze_minutes: u32,
+    ) -> Result<Self, AlarmError> {
+        let alarm = Self {
+            label,
+            time,
+            weekdays,
+            snooze_minutes,
+            enabled: false,
+            next_ring: None,
+            ringing: false,
+            snoozed: false,
+        };
+
+        alarm.validate().map(|()| alarm)
+    }
+
+    /// Alarms are edited through [`Alarm::new`], but the config file can be edited by
+    /// hand, so loaded alarms are checked again before they're scheduled.
+    pub fn validate(&self) -> Result<(), AlarmError> {
+        if self.label.chars().count() > MAX_LABEL_LENGTH {
+            return Err(AlarmError::LabelTooLong);
+        }
+
+        if let Some(weekday) = self
+            .weekdays
+            .iter()
+            .enumerate()
+            .find_map(|(index, weekday)| {
+                self.weekdays[..index].contains(weekday).then_some(*weekday)
+            })
+        {
+            return Err(AlarmError::DuplicateWeekday(weekday));
+        }
+
+        if !(1..=MAX_SNOOZE_MINUTES).contains(&self.snooze_minutes) {
+            return Err(AlarmError::SnoozeOutOfRange);
+        }
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn is_recurring(&self) -> bool {
+        !self.weekdays.is_empty()
+    }
+
+    /// The first time strictly after `after` that the alarm goes off, in `after`'s
+    /// time zone. A time skipped by a DST change rings as soon as the clocks have
+    /// jumped, and a repeated one rings the first time round.
+    #[must_use]
+    pub fn next_occurrence<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let today = after.date_naive();
+
+        // A week and a day, so a weekly alarm whose time has passed today is found
+        (0..=7)
+            .filter_map(|days| today.checked_add_signed(TimeDelta::days(days)))
+            .filter(|date| self.weekdays.is_empty() || self.weekdays.contains(&date.weekday()))
+            .filter_map(|date| local_time(&timezone, date, self.time))
+            .find(|occurrence| occurrence > after)
+    }
+
+    /// Turns the alarm on and schedules its next occurrence after `now`.
+    pub fn schedule<Tz: TimeZone>(&mut self, now: &DateTime<Tz>) {
+        self.enabled = true;
+        self.ringing = false;
+        self.snoozed = false;
+        self.next_ring = self
+            .next_occurrence(now)
+            .map(|occurrence| occurrence.with_timezone(&Utc));
+    }
+
+    pub fn disable(&mut self) {
+        self.enabled = false;
+        self.ringing = false;
+        self.snoozed = false;
+        self.next_ring = None;
+    }
+
+    #[must_use]
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && self.next_ring.is_some_and(|next_ring| next_ring <= now)
+    }
+
+    /// Goes off: a one-off alarm turns itself off, a recurring one moves on to its
+    /// next day. Both stay ringing until snoozed or dismissed.
+    pub fn ring<Tz: TimeZone>(&mut self, now: &DateTime<Tz>) {
+        if self.is_recurring() {
+            self.schedule(now);
+        } else {
+            self.disable();
+        }
+
+        self.ringing = true;
+    }
+
+    /// Rings again in `snooze_minutes`, ahead of any regular occurrence.
+    pub fn snooze(&mut self, now: DateTime<Utc>) {
+        self.enabled = true;
+        self.ringing = false;
+        self.snoozed = true;
+        self.next_ring = Some(now + TimeDelta::minutes(i64::from(self.snooze_minutes)));
+    }
+
+    /// Stops ringing, and cancels a pending snooze.
+    pub fn dismiss<Tz: TimeZone>(&mut self, now: &DateTime<Tz>) {
+        if self.snoozed && !self.is_recurring() {
+            self.disable();
+        } else if self.snoozed {
+            self.schedule(now);
+        }
+
+        self.ringing = false;
+    }
+}
+
+fn local_time<Tz: TimeZone>(
+    timezone: &Tz,
+    date: NaiveDate,
+    time: NaiveTime,
+) -> Option<DateTime<Tz>> {
+    let naive = date.and_time(time);
+
+    match timezone.from_local_datetime(&naive) {
+        LocalResult::Single(date_time) => Some(date_time),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        // In a DST gap, which is an hour everywhere that still observes DST
+        LocalResult::None => timezone
+            .from_local_datetime(&(naive + TimeDelta::hours(1)))
+            .earliest(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+    use chrono_tz::Europe::Berlin;
+
+    use super::{Alarm, AlarmError, WEEKDAYS};
+
+    fn alarm(hour: u32, minute: u32, weekdays: &[Weekday]) -> Alarm {
+        Alarm::new(
+            "Wake up".to_owned(),
+            NaiveTime::from_hms_opt(hour, minute, 0).unwrap(),
+            weekdays.to_vec(),
+            10,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn one_off_rings_today_or_tomorrow() {
+        let tz = FixedOffset::east_opt(7 * 3600).unwrap();
+        // A Wednesday
+        let now = tz.with_ymd_and_hms(2025, 3, 5, 6, 0, 0).unwrap();
+
+        assert_eq!(
+            alarm(7, 30, &[]).next_occurrence(&now),
+            Some(tz.with_ymd_and_hms(2025, 3, 5, 7, 30, 0).unwrap())
+        );
+        assert_eq!(
+            alarm(6, 0, &[]).next_occurrence(&now),
+            Some(tz.with_ymd_and_hms(2025, 3, 6, 6, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn recurring_skips_to_the_next_chosen_weekday() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 5, 8, 0, 0).unwrap();
+
+        assert_eq!(
+            alarm(7, 0, &[Weekday::Mon, Weekday::Fri]).next_occurrence(&now),
+            Some(Utc.with_ymd_and_hms(2025, 3, 7, 7, 0, 0).unwrap())
+        );
+        // Today's has passed, so it's next week's
+        assert_eq!(
+            alarm(7, 0, &[Weekday::Wed]).next_occurrence(&now),
+            Some(Utc.with_ymd_and_hms(2025, 3, 12, 7, 0, 0).unwrap())
+        );
+        assert_eq!(
+            alarm(9, 0, &WEEKDAYS).next_occurrence(&now),
+            Some(Utc.with_ymd_and_hms(2025, 3, 5, 9, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn dst_gap_rings_after_the_jump() {
+        // Clocks in Berlin went from 02:00 to 03:00 on 2025-03-30
+        let now = Berlin.with_ymd_and_hms(2025, 3, 30, 1, 0, 0).unwrap();
+        let occurrence = alarm(2, 30, &[]).next_occurrence(&now).unwrap();
+
+        assert_eq!(
+            occurrence.naive_local(),
+            NaiveDate::from_ymd_opt(2025, 3, 30)
+                .unwrap()
+                .and_hms_opt(3, 30, 0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn dst_overlap_rings_once() {
+        // Clocks in Berlin went from 03:00 back to 02:00 on 2025-10-26
+        let now = Berlin.with_ymd_and_hms(2025, 10, 26, 1, 0, 0).unwrap();
+        let mut alarm = alarm(2, 30, &[Weekday::Sun]);
+
+        alarm.schedule(&now);
+        assert_eq!(
+            alarm.next_ring,
+            Some(Utc.with_ymd_and_hms(2025, 10, 26, 0, 30, 0).unwrap())
+        );
+
+        // The second 02:30 of the day doesn't ring it again
+        let after_ringing = alarm.next_ring.unwrap().with_timezone(&Berlin);
+        alarm.ring(&after_ringing);
+        assert_eq!(
+            alarm.next_ring,
+            Some(Utc.with_ymd_and_hms(2025, 11, 2, 1, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn one_off_disables_itself_after_ringing() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 5, 6, 0, 0).unwrap();
+        let mut alarm = alarm(7, 0, &[]);
+
+        alarm.schedule(&now);
+        let next_ring = alarm.next_ring.unwrap();
+
+        assert!(!alarm.is_due(now));
+        assert!(alarm.is_due(next_ring));
+
+        alarm.ring(&next_ring);
+        assert!(alarm.ringing);
+        assert!(!alarm.enabled);
+        assert!(!alarm.is_due(next_ring + chrono::TimeDelta::days(1)));
+    }
+
+    #[test]
+    fn snooze_rings_again_and_dismiss_cancels_it() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 5, 7, 0, 0).unwrap();
+        let mut recurring = alarm(7, 0, &[Weekday::Wed]);
+
+        recurring.schedule(&(now - chrono::TimeDelta::hours(1)));
+        recurring.ring(&now);
+        recurring.snooze(now);
+        assert!(!recurring.ringing);
+        assert_eq!(
+            recurring.next_ring,
+            Some(Utc.with_ymd_and_hms(2025, 3, 5, 7, 10, 0).unwrap())
+        );
+
+        recurring.dismiss(&now);
+        assert!(!recurring.snoozed);
+        assert_eq!(
+            recurring.next_ring,
+            Some(Utc.with_ymd_and_hms(2025, 3, 12, 7, 0, 0).unwrap())
+        );
+
+        let mut one_off = alarm(7, 0, &[]);
+        one_off.schedule(&(now - chrono::TimeDelta::hours(1)));
+        one_off.ring(&now);
+        one_off.snooze(now);
+        assert!(one_off.is_due(now + chrono::TimeDelta::minutes(10)));
+
+        one_off.dismiss(&now);
+        assert!(!one_off.enabled);
+        assert_eq!(one_off.next_ring, None);
+    }
+
+    #[test]
+    fn invalid_alarms_are_rejected() {
+        let time = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
+
+        assert_eq!(
+            Alarm::new("x".repeat(65), time, vec![], 10),
+            Err(AlarmError::LabelTooLong)
+        );
+        assert_eq!(
+            Alarm::new(
+                String::new(),
+                time,
+                vec![Weekday::Mon, Weekday::Tue, Weekday::Mon],
+                10
+            ),
+            Err(AlarmError::DuplicateWeekday(Weekday::Mon))
+        );
+        assert_eq!(
+            Alarm::new(String::new(), time, vec![], 0),
+            Err(AlarmError::SnoozeOutOfRange)
+        );
+        assert_eq!(
+            Alarm::new(String::new(), time, vec![], 61),
+            Err(AlarmError::SnoozeOutOfRange)
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::cell::Cell;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use chrono::{NaiveTime, Timelike};
+use cosmic::{
+    iced::{
+        mouse,
+        widget::canvas::{self, LineCap, Path, Stroke},
+        Color, Point, Rectangle, Vector,
+    },
+    Renderer, Theme,
+};
+use serde::{Deserialize, Serialize};
+
+/// How often the hands move. Only they are redrawn, the face and its marks are cached.
+pub const FRAME: Duration = Duration::from_millis(100);
+
+/// The face fills the width it's given, up to this side, so that the digital time and
+/// the settings under it stay in view on wide windows.
+pub const MAX_SIDE: f32 = 320.0;
+
+/// Which clock the Clock page shows, persisted in `UniConfig`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockFace {
+    Analog,
+    #[default]
+    Digital,
+    Both,
+}
+
+impl ClockFace {
+    pub const ALL: [Self; 3] = [Self::Analog, Self::Digital, Self::Both];
+
+    #[must_use]
+    pub fn shows_analog(self) -> bool {
+        matches!(self, Self::Analog | Self::Both)
+    }
+
+    #[must_use]
+    pub fn shows_digital(self) -> bool {
+        matches!(self, Self::Digital | Self::Both)
+    }
+}
+
+/// A canvas program drawing `time` on a round face as big as its bounds allow.
+pub struct AnalogClock {
+    pub time: NaiveTime,
+}
+
+/// The face and its marks, redrawn only when the canvas is resized or the theme's colors
+/// change.
+#[derive(Default)]
+pub struct FaceCache {
+    cache: canvas::Cache,
+    colors: Cell<Option<(Color, Color)>>,
+}
+
+/// Clockwise from 12 o'clock, in turns.
+struct HandAngles {
+    hour: f32,
+    minute: f32,
+    second: f32,
+}
+
+impl HandAngles {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(time: NaiveTime) -> Self {
+        // Sub-second precision makes the second hand sweep rather than tick
+        let seconds = f64::from(time.second()) + f64::from(time.nanosecond() % 1_000_000_000) / 1e9;
+        let minutes = f64::from(time.minute()) + seconds / 60.0;
+        let hours = f64::from(time.hour() % 12) + minutes / 60.0;
+
+        Self {
+            hour: (hours / 12.0) as f32,
+            minute: (minutes / 60.0) as f32,
+            second: (seconds / 60.0) as f32,
+        }
+    }
+}
+
+impl AnalogClock {
+    fn draw_hand(frame: &mut canvas::Frame, turns: f32, length: f32, stroke: Stroke) {
+        frame.with_save(|frame| {
+            frame.rotate(turns * TAU);
+            frame.stroke(&Path::line(Point::ORIGIN, Point::new(0.0, -length)), stroke);
+        });
+    }
+}
+
+impl<Message> canvas::Program<Message, Theme> for AnalogClock {
+    type State = FaceCache;
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let cosmic = theme.cosmic();
+        let face_color = Color::from(cosmic.primary.base);
+        let mark_color = Color::from(cosmic.on_bg_color());
+        let second_hand_color = Color::from(cosmic.accent_color());
+
+        let center = Point::new(bounds.width / 2.0, bounds.height / 2.0);
+        let radius = bounds.width.min(bounds.height) / 2.0;
+        let width = radius / 40.0;
+        let angles = HandAngles::new(self.time);
+
+        let colors = Some((face_color, mark_color));
+
+        if state.colors.replace(colors) != colors {
+            state.cache.clear();
+        }
+
+        let face = state.cache.draw(renderer, bounds.size(), |frame| {
+            frame.fill(&Path::circle(center, radius), face_color);
+            frame.translate(Vector::new(center.x, center.y));
+
+            for mark in 0..60_u8 {
+                let is_hour = mark % 5 == 0;
+                let length = if is_hour { radius / 8.0 } else { radius / 20.0 };
+
+                frame.with_save(|frame| {
+                    frame.rotate(f32::from(mark) / 60.0 * TAU);
+                    frame.stroke(
+                        &Path::line(
+                            Point::new(0.0, -radius * 0.95),
+                            Point::new(0.0, -radius * 0.95 + length),
+                        ),
+                        Stroke::default()
+                            .with_color(mark_color)
+                            .with_width(if is_hour { width * 1.5 } else { width / 2.0 }),
+                    );
+                });
+            }
+        });
+
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        frame.translate(Vector::new(center.x, center.y));
+
+        let hand = |width: f32, color: Color| {
+            Stroke::default()
+                .with_color(color)
+                .with_width(width)
+                .with_line_cap(LineCap::Round)
+        };
+
+        Self::draw_hand(
+            &mut frame,
+            angles.hour,
+            radius * 0.5,
+            hand(width * 3.0, mark_color),
+        );
+        Self::draw_hand(
+            &mut frame,
+            angles.minute,
+            radius * 0.75,
+            hand(width * 2.0, mark_color),
+        );
+        Self::draw_hand(
+            &mut frame,
+            angles.second,
+            radius * 0.85,
+            hand(width, second_hand_color),
+        );
+        frame.fill(&Path::circle(Point::ORIGIN, width * 2.5), second_hand_color);
+
+        vec![face, frame.into_geometry()]
+    }
+}
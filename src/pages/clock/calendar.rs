@@ -0,0 +1,329 @@
+use std::io::BufRead;
+
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Multi-day events longer than this are cut short, so a malformed end date can't
+/// flood the calendar.
+const MAX_EVENT_DAYS: i64 = 31;
+
+pub const MONTH_PATTERN: &str = "%B %Y";
+
+/// A day off imported from an ICS file, persisted in `UniConfig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+    /// Falls on the same day every year, such as New Year's Day.
+    pub yearly: bool,
+}
+
+impl Holiday {
+    #[must_use]
+    pub fn falls_on(&self, date: NaiveDate) -> bool {
+        if self.yearly {
+            self.date.month() == date.month()
+                && self.date.day() == date.day()
+                && self.date.year() <= date.year()
+        } else {
+            self.date == date
+        }
+    }
+}
+
+/// A row of the month view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarWeek {
+    /// The ISO week of the row's Monday, which is the ISO week of the whole row when
+    /// weeks start on a Monday.
+    pub iso_week: u32,
+    pub days: [NaiveDate; 7],
+}
+
+/// The first day of `date`'s month.
+#[must_use]
+pub fn month_of(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[must_use]
+pub fn add_months(month: NaiveDate, months: i32) -> NaiveDate {
+    let shifted = if months < 0 {
+        month.checked_sub_months(Months::new(months.unsigned_abs()))
+    } else {
+        month.checked_add_months(Months::new(months.unsigned_abs()))
+    };
+
+    shifted.unwrap_or(month)
+}
+
+/// The weekdays in the order the month view's columns show them.
+#[must_use]
+pub fn weekdays_from(first_weekday: Weekday) -> [Weekday; 7] {
+    let mut weekday = first_weekday;
+
+    [(); 7].map(|()| {
+        let current = weekday;
+        weekday = weekday.succ();
+        current
+    })
+}
+
+/// The weeks overlapping `month`, with the days of the neighbouring months that fill
+/// them out.
+#[must_use]
+pub fn month_weeks(month: NaiveDate, first_weekday: Weekday) -> Vec<CalendarWeek> {
+    let month = month_of(month);
+    let leading_days = month.weekday().days_since(first_weekday);
+    let Some(mut week_start) = month.checked_sub_signed(TimeDelta::days(i64::from(leading_days)))
+    else {
+        return vec![];
+    };
+    let mut weeks = vec![];
+
+    while week_start <= month || week_start.month() == month.month() {
+        let days = week_start.iter_days().take(7).collect::<Vec<NaiveDate>>();
+        let Ok(days) = <[NaiveDate; 7]>::try_from(days) else {
+            break;
+        };
+        let monday = days
+            .iter()
+            .find(|day| day.weekday() == Weekday::Mon)
+            .unwrap_or(&days[0]);
+
+        weeks.push(CalendarWeek {
+            iso_week: monday.iso_week().week(),
+            days,
+        });
+
+        match week_start.checked_add_signed(TimeDelta::days(7)) {
+            Some(next_week_start) => week_start = next_week_start,
+            None => break,
+        }
+    }
+
+    weeks
+}
+
+/// The all-day events of every calendar in an ICS file. Events with a time of day
+/// are read as falling on their local start date. Events repeating every year on the
+/// same day recur, any other repeat rule imports just the listed occurrence.
+pub fn parse_holidays(reader: impl BufRead) -> Result<Vec<Holiday>, String> {
+    parse_holidays_with_local(reader, &chrono::Local)
+}
+
+/// Like [`parse_holidays`], with `local` standing in for the system's zone.
+pub fn parse_holidays_with_local<Tz: TimeZone>(
+    reader: impl BufRead,
+    local: &Tz,
+) -> Result<Vec<Holiday>, String> {
+    let mut holidays = vec![];
+
+    for calendar in ical::IcalParser::new(reader) {
+        let calendar = calendar.map_err(|e| e.to_string())?;
+
+        for event in calendar.events {
+            let property = |name: &str| {
+                event
+                    .properties
+                    .iter()
+                    .find(|property| property.name.eq_ignore_ascii_case(name))
+                    .and_then(|property| property.value.as_deref())
+            };
+
+            let Some(start) = property("DTSTART").and_then(|value| parse_ics_date(value, local))
+            else {
+                continue;
+            };
+            let end = property("DTEND")
+                .and_then(|value| parse_ics_date(value, local))
+                .filter(|end| *end > start)
+                .unwrap_or(start + TimeDelta::days(1))
+                .min(start + TimeDelta::days(MAX_EVENT_DAYS));
+            let name = property("SUMMARY").unwrap_or_default().trim().to_owned();
+            let yearly = property("RRULE").is_some_and(is_same_day_yearly);
+
+            holidays.extend(
+                start
+                    .iter_days()
+                    .take_while(|date| *date < end)
+                    .map(|date| Holiday {
+                        date,
+                        name: name.clone(),
+                        yearly,
+                    }),
+            );
+        }
+    }
+
+    holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+    holidays.dedup();
+
+    Ok(holidays)
+}
+
+/// `FREQ=YEARLY` on its own. `BY*` parts move the day from year to year, such as
+/// Easter's or a "last Monday of May", and `UNTIL` or `COUNT` end the repeats, none of
+/// which [`Holiday::yearly`] can follow.
+fn is_same_day_yearly(rrule: &str) -> bool {
+    let mut is_yearly = false;
+
+    for part in rrule.split(';').filter(|part| !part.is_empty()) {
+        let (name, value) = part.split_once('=').unwrap_or((part, ""));
+
+        if name.eq_ignore_ascii_case("FREQ") {
+            is_yearly = value.eq_ignore_ascii_case("YEARLY");
+        } else if !(name.eq_ignore_ascii_case("WKST")
+            || (name.eq_ignore_ascii_case("INTERVAL") && value == "1"))
+        {
+            return false;
+        }
+    }
+
+    is_yearly
+}
+
+/// `20250101`, the date of a floating `20250101T090000`, or the local date of a UTC
+/// `20250101T230000Z`.
+fn parse_ics_date<Tz: TimeZone>(value: &str, local: &Tz) -> Option<NaiveDate> {
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|date_time| date_time.and_utc().with_timezone(local).date_naive());
+    }
+
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, Weekday};
+
+    use super::{month_weeks, parse_holidays, parse_holidays_with_local, weekdays_from};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_the_chosen_day() {
+        // 2025-06-01 is a Sunday
+        let monday_first = month_weeks(date(2025, 6, 1), Weekday::Mon);
+        let sunday_first = month_weeks(date(2025, 6, 1), Weekday::Sun);
+
+        assert_eq!(monday_first.len(), 6);
+        assert_eq!(monday_first[0].days[0], date(2025, 5, 26));
+        assert_eq!(monday_first[0].iso_week, 22);
+        assert_eq!(monday_first[5].days[6], date(2025, 7, 6));
+
+        assert_eq!(sunday_first.len(), 5);
+        assert_eq!(sunday_first[0].days[0], date(2025, 6, 1));
+        assert_eq!(sunday_first[0].iso_week, 23);
+        assert_eq!(sunday_first[4].days[6], date(2025, 7, 5));
+
+        assert_eq!(
+            weekdays_from(Weekday::Sat)[..3],
+            [Weekday::Sat, Weekday::Sun, Weekday::Mon]
+        );
+    }
+
+    #[test]
+    fn iso_weeks_cross_years() {
+        // 2024-12-30 is in week 1 of 2025, 2021-01-01 in week 53 of 2020
+        let december = month_weeks(date(2024, 12, 1), Weekday::Mon);
+        let january = month_weeks(date(2021, 1, 1), Weekday::Mon);
+
+        assert_eq!(december.last().unwrap().iso_week, 1);
+        assert_eq!(january[0].iso_week, 53);
+    }
+
+    #[test]
+    fn parses_all_day_and_yearly_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250101\r\n\
+SUMMARY:New Year's Day\r\n\
+RRULE:FREQ=YEARLY\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250128\r\n\
+DTEND;VALUE=DATE:20250131\r\n\
+SUMMARY:Lunar New Year\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:No date\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let holidays = parse_holidays(ics.as_bytes()).unwrap();
+
+        assert_eq!(holidays.len(), 4);
+        assert!(holidays[0].yearly);
+        assert!(holidays[0].falls_on(date(2030, 1, 1)));
+        assert!(!holidays[0].falls_on(date(2024, 1, 1)));
+        assert_eq!(holidays[3].date, date(2025, 1, 30));
+        assert!(!holidays[3].falls_on(date(2026, 1, 30)));
+    }
+
+    #[test]
+    fn only_same_day_rules_recur() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250526\r\n\
+SUMMARY:Memorial Day\r\n\
+RRULE:FREQ=YEARLY;BYMONTH=5;BYDAY=-1MO\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20250704\r\n\
+SUMMARY:Independence Day\r\n\
+RRULE:FREQ=YEARLY;INTERVAL=1\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART;VALUE=DATE:20251225\r\n\
+SUMMARY:Office closed\r\n\
+RRULE:FREQ=YEARLY;COUNT=3\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let holidays = parse_holidays(ics.as_bytes()).unwrap();
+        let yearly: Vec<(&str, bool)> = holidays
+            .iter()
+            .map(|holiday| (holiday.name.as_str(), holiday.yearly))
+            .collect();
+
+        assert_eq!(
+            yearly,
+            [
+                ("Memorial Day", false),
+                ("Independence Day", true),
+                ("Office closed", false),
+            ]
+        );
+        assert!(!holidays[0].falls_on(date(2026, 5, 26)));
+    }
+
+    #[test]
+    fn utc_times_fall_on_their_local_date() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20241231T230000Z\r\n\
+DTEND:20250101T230000Z\r\n\
+SUMMARY:New Year's Day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20250105T230000\r\n\
+SUMMARY:Floating\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let holidays =
+            parse_holidays_with_local(ics.as_bytes(), &FixedOffset::east_opt(3600).unwrap())
+                .unwrap();
+        let dates: Vec<NaiveDate> = holidays.iter().map(|holiday| holiday.date).collect();
+
+        assert_eq!(dates, [date(2025, 1, 1), date(2025, 1, 5)]);
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone,
+    Timelike, Weekday,
+};
+use chrono_tz::Tz;
+
+pub const DATE_INPUT_PATTERN: &str = "%Y-%m-%d";
+pub const TIME_INPUT_PATTERN: &str = "%H:%M";
+
+/// Working hours are 09:00 to 17:00, Monday to Friday, in every zone.
+const WORKING_HOURS: std::ops::Range<u32> = 9..17;
+
+/// `None` is the system's own zone, which may not have an IANA name to go by.
+pub type Zone = Option<Tz>;
+
+/// `naive` as a wall-clock time in `zone`, the earlier one when DST makes it
+/// ambiguous, and `None` when DST skips it.
+#[must_use]
+pub fn resolve(zone: Zone, naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    match zone {
+        Some(tz) => earliest(&tz, naive),
+        None => earliest(&chrono::Local, naive),
+    }
+}
+
+fn earliest<T: TimeZone>(tz: &T, naive: NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|date_time| date_time.fixed_offset())
+}
+
+#[must_use]
+pub fn in_zone(instant: DateTime<FixedOffset>, zone: Zone) -> DateTime<FixedOffset> {
+    match zone {
+        Some(tz) => instant.with_timezone(&tz).fixed_offset(),
+        None => instant.with_timezone(&chrono::Local).fixed_offset(),
+    }
+}
+
+#[must_use]
+pub fn parse_input(date: &str, time: &str) -> Option<NaiveDateTime> {
+    let date = NaiveDate::parse_from_str(date.trim(), DATE_INPUT_PATTERN).ok()?;
+    let time = NaiveTime::parse_from_str(time.trim(), TIME_INPUT_PATTERN).ok()?;
+
+    Some(date.and_time(time))
+}
+
+#[must_use]
+pub fn is_working_hour(date_time: &DateTime<FixedOffset>) -> bool {
+    !matches!(date_time.weekday(), Weekday::Sat | Weekday::Sun)
+        && WORKING_HOURS.contains(&date_time.hour())
+}
+
+/// An hour of the meeting planner, as read on each zone's clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannerRow {
+    pub times: Vec<DateTime<FixedOffset>>,
+    pub is_working_everywhere: bool,
+}
+
+/// Every hour of `date` in the first of `zones`, which has 23 or 25 of them on DST
+/// changes, with the matching times in the rest. Zones that spring forward at
+/// midnight start the day at the first hour their clocks show.
+#[must_use]
+pub fn planner_rows(date: NaiveDate, zones: &[Zone]) -> Vec<PlannerRow> {
+    let Some(first_zone) = zones.first().copied() else {
+        return vec![];
+    };
+    let Some(start) = (0..24).find_map(|hour| resolve(first_zone, date.and_hms_opt(hour, 0, 0)?))
+    else {
+        return vec![];
+    };
+
+    (0..25)
+        .map(|hour| start + TimeDelta::hours(hour))
+        .take_while(|instant| in_zone(*instant, first_zone).date_naive() == date)
+        .map(|instant| {
+            let times: Vec<DateTime<FixedOffset>> =
+                zones.iter().map(|zone| in_zone(instant, *zone)).collect();
+
+            PlannerRow {
+                is_working_everywhere: times.iter().all(is_working_hour),
+                times,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Timelike};
+    use chrono_tz::America::{New_York, Santiago};
+    use chrono_tz::Asia::{Beirut, Tokyo};
+    use chrono_tz::Europe::London;
+
+    use super::{is_working_hour, planner_rows};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn working_hours_are_nine_to_five_on_weekdays() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        // A Friday, then a Saturday
+        let at = |day, hour, minute| tz.with_ymd_and_hms(2025, 3, day, hour, minute, 0).unwrap();
+
+        assert!(!is_working_hour(&at(7, 8, 59)));
+        assert!(is_working_hour(&at(7, 9, 0)));
+        assert!(is_working_hour(&at(7, 16, 59)));
+        assert!(!is_working_hour(&at(7, 17, 0)));
+        assert!(!is_working_hour(&at(8, 10, 0)));
+    }
+
+    #[test]
+    fn plans_every_hour_of_the_first_zone() {
+        // A Wednesday, London on GMT and Tokyo nine hours ahead.
+        let rows = planner_rows(date(2025, 1, 15), &[Some(London), Some(Tokyo)]);
+
+        assert_eq!(rows.len(), 24);
+        assert_eq!(rows[0].times[0].hour(), 0);
+        assert_eq!(rows[0].times[1].hour(), 9);
+        // Nobody works when the other is at their desk.
+        assert!(rows.iter().all(|row| !row.is_working_everywhere));
+    }
+
+    #[test]
+    fn dst_changes_plan_23_or_25_hours() {
+        assert_eq!(planner_rows(date(2025, 3, 9), &[Some(New_York)]).len(), 23);
+        assert_eq!(planner_rows(date(2025, 11, 2), &[Some(New_York)]).len(), 25);
+    }
+
+    #[test]
+    fn dst_skipping_midnight_starts_at_one() {
+        for (zone, date) in [(Santiago, date(2025, 9, 7)), (Beirut, date(2025, 3, 30))] {
+            let rows = planner_rows(date, &[Some(zone), Some(London)]);
+
+            assert_eq!(rows.len(), 23, "{zone}");
+            assert_eq!(rows[0].times[0].hour(), 1, "{zone}");
+            assert_eq!(rows[22].times[0].hour(), 23, "{zone}");
+        }
+    }
+}
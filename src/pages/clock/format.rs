@@ -0,0 +1,57 @@
+use chrono::format::{Item, StrftimeItems};
+use serde::{Deserialize, Serialize};
+
+/// Shown as the placeholder of the custom pattern input.
+pub const DEFAULT_CUSTOM_PATTERN: &str = "%H:%M:%S";
+
+/// The locale's own date, with the weekday spelled out.
+pub const DATE_PATTERN: &str = "%A, %x";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HourCycle {
+    #[default]
+    H24,
+    H12,
+}
+
+/// How the Clock page shows times, persisted in `UniConfig`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockFormat {
+    pub hour_cycle: HourCycle,
+    pub show_date: bool,
+    /// A strftime pattern for the big clock, replacing the hour cycle's. The page only
+    /// saves patterns [`is_valid_pattern`] accepts, but the config file can be edited by
+    /// hand, so [`ClockFormat::time_pattern`] checks it again.
+    pub custom_pattern: Option<String>,
+}
+
+impl ClockFormat {
+    /// The pattern of the big clock: the custom pattern when it's valid, otherwise the
+    /// hour cycle's.
+    #[must_use]
+    pub fn time_pattern(&self) -> &str {
+        match (&self.custom_pattern, self.hour_cycle) {
+            (Some(custom_pattern), _) if is_valid_pattern(custom_pattern) => custom_pattern,
+            (_, HourCycle::H24) => "%H:%M:%S",
+            (_, HourCycle::H12) => "%I:%M:%S %p",
+        }
+    }
+
+    /// The pattern of smaller clocks, such as the world clock's, which skip seconds
+    /// and the custom pattern.
+    #[must_use]
+    pub fn short_time_pattern(&self) -> &'static str {
+        match self.hour_cycle {
+            HourCycle::H24 => "%H:%M",
+            HourCycle::H12 => "%I:%M %p",
+        }
+    }
+}
+
+/// chrono panics when displaying a time with a malformed pattern, so custom patterns
+/// are checked before they're used.
+#[must_use]
+pub fn is_valid_pattern(pattern: &str) -> bool {
+    !pattern.trim().is_empty()
+        && StrftimeItems::new(pattern).all(|item| !matches!(item, Item::Error))
+}
@@ -0,0 +1,26 @@
+use crate::fl;
+
+/// From the freedesktop sound theme, played through the notification server's
+/// `sound-name` hint.
+pub const SOUND_NAME: &str = "alarm-clock-elapsed";
+
+/// Shows a desktop notification, off the UI thread since it's a blocking D-Bus call.
+pub async fn notify(summary: String, body: String, play_sound: bool) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let mut notification = notify_rust::Notification::new();
+
+        notification
+            .appname(&fl!("app-title"))
+            .icon("alarm-symbolic")
+            .summary(&summary)
+            .body(&body);
+
+        if play_sound {
+            notification.sound_name(SOUND_NAME);
+        }
+
+        notification.show().map(|_| ()).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,562 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt as _;
+
+pub const MAX_MINUTES: u32 = 240;
+pub const MAX_SESSIONS_PER_CYCLE: u32 = 12;
+
+/// Completed focus sessions, one JSON object per line so logging one is an append.
+const SESSION_LOG_FILE_NAME: &str = "focus_sessions.jsonl";
+
+/// Persisted in `UniConfig`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PomodoroSettings {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// Work sessions in a cycle, the last of which is followed by the long break.
+    pub sessions_per_cycle: u32,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            sessions_per_cycle: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PomodoroSetting {
+    WorkMinutes,
+    ShortBreakMinutes,
+    LongBreakMinutes,
+    SessionsPerCycle,
+}
+
+impl PomodoroSetting {
+    pub const ALL: [Self; 4] = [
+        Self::WorkMinutes,
+        Self::ShortBreakMinutes,
+        Self::LongBreakMinutes,
+        Self::SessionsPerCycle,
+    ];
+
+    #[must_use]
+    pub fn get(self, settings: &PomodoroSettings) -> u32 {
+        match self {
+            Self::WorkMinutes => settings.work_minutes,
+            Self::ShortBreakMinutes => settings.short_break_minutes,
+            Self::LongBreakMinutes => settings.long_break_minutes,
+            Self::SessionsPerCycle => settings.sessions_per_cycle,
+        }
+    }
+
+    /// `settings` with this setting changed, if `input` is in range for it.
+    #[must_use]
+    pub fn parse(self, settings: &PomodoroSettings, input: &str) -> Option<PomodoroSettings> {
+        let max = match self {
+            Self::SessionsPerCycle => MAX_SESSIONS_PER_CYCLE,
+            _ => MAX_MINUTES,
+        };
+        let value = input
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|value| (1..=max).contains(value))?;
+        let mut settings = *settings;
+
+        *match self {
+            Self::WorkMinutes => &mut settings.work_minutes,
+            Self::ShortBreakMinutes => &mut settings.short_break_minutes,
+            Self::LongBreakMinutes => &mut settings.long_break_minutes,
+            Self::SessionsPerCycle => &mut settings.sessions_per_cycle,
+        } = value;
+
+        Some(settings)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Phase {
+    #[default]
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    #[must_use]
+    pub fn minutes(self, settings: &PomodoroSettings) -> u32 {
+        match self {
+            Phase::Work => settings.work_minutes,
+            Phase::ShortBreak => settings.short_break_minutes,
+            Phase::LongBreak => settings.long_break_minutes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum PhaseState {
+    #[default]
+    Idle,
+    Running {
+        ends_at: DateTime<Utc>,
+    },
+    Paused {
+        remaining_secs: u64,
+        paused_at: DateTime<Utc>,
+    },
+}
+
+/// A completed work phase, as logged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSession {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// The time between start and end that wasn't spent paused.
+    pub focus_minutes: u32,
+}
+
+/// A phase that ran out, and the one started in its place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub finished: Phase,
+    pub next: Phase,
+    /// Set when a work phase finished.
+    pub session: Option<FocusSession>,
+}
+
+/// The running Pomodoro, kept in memory only: a restart begins a fresh cycle.
+#[derive(Debug, Clone, Default)]
+pub struct Pomodoro {
+    pub phase: Phase,
+    /// Work sessions finished in the current cycle.
+    pub completed_in_cycle: u32,
+    state: PhaseState,
+    /// When the current phase was first started, before any pauses.
+    phase_started_at: Option<DateTime<Utc>>,
+    /// How long the current phase has spent paused.
+    paused_for: TimeDelta,
+}
+
+impl Pomodoro {
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, PhaseState::Running { .. })
+    }
+
+    #[must_use]
+    pub fn remaining_secs(&self, now: DateTime<Utc>, settings: &PomodoroSettings) -> u64 {
+        match self.state {
+            PhaseState::Idle => u64::from(self.phase.minutes(settings)) * 60,
+            PhaseState::Running { ends_at } => u64::try_from((ends_at - now).num_milliseconds())
+                .unwrap_or_default()
+                .div_ceil(1000),
+            PhaseState::Paused { remaining_secs, .. } => remaining_secs,
+        }
+    }
+
+    pub fn start(&mut self, now: DateTime<Utc>, settings: &PomodoroSettings) {
+        if !self.is_running() {
+            if let PhaseState::Paused { paused_at, .. } = self.state {
+                self.paused_for += now - paused_at;
+            }
+
+            self.state = PhaseState::Running {
+                ends_at: now
+                    + TimeDelta::seconds(
+                        i64::try_from(self.remaining_secs(now, settings)).unwrap_or_default(),
+                    ),
+            };
+            self.phase_started_at.get_or_insert(now);
+        }
+    }
+
+    pub fn pause(&mut self, now: DateTime<Utc>, settings: &PomodoroSettings) {
+        if self.is_running() {
+            self.state = PhaseState::Paused {
+                remaining_secs: self.remaining_secs(now, settings),
+                paused_at: now,
+            };
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Moves on to the next phase without logging the current one, keeping the
+    /// timer running if it was.
+    pub fn skip(&mut self, now: DateTime<Utc>, settings: &PomodoroSettings) {
+        let was_running = self.is_running();
+
+        self.enter_next_phase(settings);
+
+        if was_running {
+            self.start(now, settings);
+        }
+    }
+
+    /// Finishes the current phase if it ran out by `now` and starts the next one.
+    pub fn advance_if_due(
+        &mut self,
+        now: DateTime<Utc>,
+        settings: &PomodoroSettings,
+    ) -> Option<Transition> {
+        let PhaseState::Running { ends_at } = self.state else {
+            return None;
+        };
+
+        if ends_at > now {
+            return None;
+        }
+
+        let finished = self.phase;
+        let session = (finished == Phase::Work).then(|| {
+            let started_at = self.phase_started_at.unwrap_or(ends_at);
+            let focus_secs = (ends_at - started_at - self.paused_for).num_seconds();
+
+            FocusSession {
+                started_at,
+                ended_at: ends_at,
+                focus_minutes: u32::try_from((focus_secs + 30) / 60).unwrap_or_default(),
+            }
+        });
+
+        self.enter_next_phase(settings);
+        self.start(now, settings);
+
+        Some(Transition {
+            finished,
+            next: self.phase,
+            session,
+        })
+    }
+
+    fn enter_next_phase(&mut self, settings: &PomodoroSettings) {
+        self.phase = match self.phase {
+            Phase::Work => {
+                self.completed_in_cycle += 1;
+
+                if self.completed_in_cycle >= settings.sessions_per_cycle {
+                    Phase::LongBreak
+                } else {
+                    Phase::ShortBreak
+                }
+            }
+            Phase::LongBreak => {
+                self.completed_in_cycle = 0;
+                Phase::Work
+            }
+            Phase::ShortBreak => Phase::Work,
+        };
+        self.state = PhaseState::Idle;
+        self.phase_started_at = None;
+        self.paused_for = TimeDelta::zero();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FocusTotals {
+    pub sessions: usize,
+    pub minutes: u32,
+}
+
+impl FocusTotals {
+    fn add(&mut self, session: &FocusSession) {
+        self.sessions += 1;
+        self.minutes += session.focus_minutes;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusStats {
+    pub today: FocusTotals,
+    pub this_week: FocusTotals,
+    /// Each day of the current ISO week, Monday first.
+    pub days: Vec<(NaiveDate, FocusTotals)>,
+}
+
+impl FocusStats {
+    /// Sessions count towards the local day they ended on.
+    #[must_use]
+    pub fn new<Tz: TimeZone>(sessions: &[FocusSession], now: &DateTime<Tz>) -> Self {
+        let today = now.date_naive();
+        let week = today.week(Weekday::Mon);
+        let mut days: Vec<(NaiveDate, FocusTotals)> = week
+            .first_day()
+            .iter_days()
+            .take(7)
+            .map(|date| (date, FocusTotals::default()))
+            .collect();
+        let mut stats = Self {
+            today: FocusTotals::default(),
+            this_week: FocusTotals::default(),
+            days: vec![],
+        };
+
+        for session in sessions {
+            let date = session.ended_at.with_timezone(&now.timezone()).date_naive();
+
+            if date == today {
+                stats.today.add(session);
+            }
+
+            if let Some((_, totals)) = days.iter_mut().find(|(day, _)| *day == date) {
+                totals.add(session);
+                stats.this_week.add(session);
+            }
+        }
+
+        stats.days = days;
+        stats
+    }
+}
+
+/// Under the XDG data directory, next to anything else the app keeps locally.
+#[must_use]
+pub fn session_log_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| {
+        data_dir
+            .join(env!("CARGO_PKG_NAME"))
+            .join(SESSION_LOG_FILE_NAME)
+    })
+}
+
+/// A missing log reads as no sessions, lines that don't parse are skipped.
+pub async fn load_sessions(path: PathBuf) -> Result<Vec<FocusSession>, String> {
+    match tokio::fs::read_to_string(&path).await {
+        Ok(log) => Ok(log
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(session) => Some(session),
+                Err(e) => {
+                    tracing::warn!("Skipping focus session {line:?}: {e}");
+                    None
+                }
+            })
+            .collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub async fn append_session(path: PathBuf, session: FocusSession) -> Result<(), String> {
+    let mut line = serde_json::to_string(&session).map_err(|e| e.to_string())?;
+    line.push('\n');
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    file.write_all(line.as_bytes())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta, TimeZone, Utc};
+
+    use super::{FocusSession, FocusStats, FocusTotals, Phase, Pomodoro, PomodoroSettings};
+
+    fn settings() -> PomodoroSettings {
+        PomodoroSettings {
+            sessions_per_cycle: 2,
+            ..PomodoroSettings::default()
+        }
+    }
+
+    fn minutes_after(start: DateTime<Utc>, minutes: i64) -> DateTime<Utc> {
+        start + TimeDelta::minutes(minutes)
+    }
+
+    fn session(ended_at: DateTime<Utc>, focus_minutes: u32) -> FocusSession {
+        FocusSession {
+            started_at: ended_at - TimeDelta::minutes(focus_minutes.into()),
+            ended_at,
+            focus_minutes,
+        }
+    }
+
+    #[test]
+    fn cycles_through_work_and_breaks() {
+        let settings = settings();
+        let start = Utc.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap();
+        let mut pomodoro = Pomodoro::default();
+
+        pomodoro.start(start, &settings);
+        assert_eq!(
+            pomodoro.advance_if_due(minutes_after(start, 24), &settings),
+            None
+        );
+
+        let transition = pomodoro
+            .advance_if_due(minutes_after(start, 25), &settings)
+            .unwrap();
+        assert_eq!(
+            (transition.finished, transition.next),
+            (Phase::Work, Phase::ShortBreak)
+        );
+        assert_eq!(
+            transition.session,
+            Some(FocusSession {
+                started_at: start,
+                ended_at: minutes_after(start, 25),
+                focus_minutes: 25,
+            })
+        );
+
+        let transition = pomodoro
+            .advance_if_due(minutes_after(start, 30), &settings)
+            .unwrap();
+        assert_eq!(
+            (transition.finished, transition.next),
+            (Phase::ShortBreak, Phase::Work)
+        );
+        assert_eq!(transition.session, None);
+
+        let transition = pomodoro
+            .advance_if_due(minutes_after(start, 55), &settings)
+            .unwrap();
+        assert_eq!(
+            (transition.finished, transition.next),
+            (Phase::Work, Phase::LongBreak)
+        );
+        assert_eq!(pomodoro.completed_in_cycle, 2);
+
+        let transition = pomodoro
+            .advance_if_due(minutes_after(start, 70), &settings)
+            .unwrap();
+        assert_eq!(
+            (transition.finished, transition.next),
+            (Phase::LongBreak, Phase::Work)
+        );
+        assert_eq!(pomodoro.completed_in_cycle, 0);
+        assert!(pomodoro.is_running());
+    }
+
+    #[test]
+    fn paused_time_is_not_focus_time() {
+        let settings = settings();
+        let start = Utc.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap();
+        let mut pomodoro = Pomodoro::default();
+
+        pomodoro.start(start, &settings);
+        pomodoro.pause(minutes_after(start, 10), &settings);
+        assert_eq!(
+            pomodoro.remaining_secs(minutes_after(start, 40), &settings),
+            15 * 60
+        );
+        pomodoro.start(minutes_after(start, 20), &settings);
+
+        let session = pomodoro
+            .advance_if_due(minutes_after(start, 35), &settings)
+            .unwrap()
+            .session
+            .unwrap();
+        assert_eq!(session.started_at, start);
+        assert_eq!(session.ended_at, minutes_after(start, 35));
+        assert_eq!(session.focus_minutes, 25);
+    }
+
+    #[test]
+    fn skipping_logs_nothing_and_keeps_running() {
+        let settings = settings();
+        let start = Utc.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap();
+        let mut pomodoro = Pomodoro::default();
+
+        pomodoro.start(start, &settings);
+        pomodoro.skip(minutes_after(start, 5), &settings);
+        assert_eq!(pomodoro.phase, Phase::ShortBreak);
+        assert_eq!(pomodoro.completed_in_cycle, 1);
+        assert!(pomodoro.is_running());
+        assert_eq!(
+            pomodoro.remaining_secs(minutes_after(start, 5), &settings),
+            5 * 60
+        );
+
+        pomodoro.pause(minutes_after(start, 6), &settings);
+        pomodoro.skip(minutes_after(start, 7), &settings);
+        assert_eq!(pomodoro.phase, Phase::Work);
+        assert!(!pomodoro.is_running());
+        assert_eq!(
+            pomodoro.remaining_secs(minutes_after(start, 7), &settings),
+            25 * 60
+        );
+        assert_eq!(
+            pomodoro.advance_if_due(minutes_after(start, 60), &settings),
+            None
+        );
+    }
+
+    #[test]
+    fn buckets_sessions_by_local_day_of_the_week() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        // A Wednesday
+        let now = tz.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap();
+        let sessions = [
+            // The Sunday before, so last week
+            session(Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap(), 25),
+            // Monday
+            session(Utc.with_ymd_and_hms(2025, 6, 2, 10, 0, 0).unwrap(), 25),
+            // Tuesday in UTC, but already Wednesday locally
+            session(Utc.with_ymd_and_hms(2025, 6, 3, 23, 30, 0).unwrap(), 20),
+            session(Utc.with_ymd_and_hms(2025, 6, 4, 8, 0, 0).unwrap(), 25),
+        ];
+
+        let stats = FocusStats::new(&sessions, &now);
+
+        assert_eq!(
+            stats.today,
+            FocusTotals {
+                sessions: 2,
+                minutes: 45,
+            }
+        );
+        assert_eq!(
+            stats.this_week,
+            FocusTotals {
+                sessions: 3,
+                minutes: 70,
+            }
+        );
+        assert_eq!(stats.days.len(), 7);
+        assert_eq!(
+            stats.days[0],
+            (
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+                FocusTotals {
+                    sessions: 1,
+                    minutes: 25,
+                }
+            )
+        );
+        assert_eq!(stats.days[1].1, FocusTotals::default());
+        assert_eq!(stats.days[2].1, stats.today);
+        assert_eq!(
+            stats.days[6].0,
+            NaiveDate::from_ymd_opt(2025, 6, 8).unwrap()
+        );
+    }
+}
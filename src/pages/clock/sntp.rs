@@ -0,0 +1,251 @@
+use std::{fmt, net::SocketAddr, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::net::UdpSocket;
+
+pub const DEFAULT_SERVER: &str = "pool.ntp.org";
+pub const DEFAULT_PORT: u16 = 123;
+pub const DEFAULT_WARNING_THRESHOLD_MS: u32 = 100;
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+const PACKET_LENGTH: usize = 48;
+/// Seconds from the NTP era, 1900-01-01, to the Unix epoch.
+const NTP_TO_UNIX_SECONDS: i64 = 2_208_988_800;
+/// Leap indicator unknown, version 4, client mode.
+const CLIENT_HEADER: u8 = 0b00_100_011;
+const SERVER_MODE: u8 = 4;
+
+/// The outcome of one SNTP exchange, as in RFC 4330.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SntpSample {
+    /// How far the server's clock is ahead of ours, negative when ours is ahead.
+    pub offset: TimeDelta,
+    /// Time spent on the network, excluding the server's processing time.
+    pub round_trip_delay: TimeDelta,
+    pub stratum: u8,
+}
+
+impl SntpSample {
+    #[must_use]
+    pub fn exceeds(&self, threshold_ms: u32) -> bool {
+        self.offset.abs() > TimeDelta::milliseconds(i64::from(threshold_ms))
+    }
+}
+
+#[derive(Debug)]
+pub enum SntpError {
+    Io(std::io::Error),
+    Unresolved(String),
+    Timeout,
+    InvalidResponse(&'static str),
+    /// The server refused to answer, with its four letter reason, such as `RATE`.
+    KissOfDeath(String),
+}
+
+impl fmt::Display for SntpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SntpError::Io(e) => write!(f, "{e}"),
+            SntpError::Unresolved(server) => write!(f, "couldn't resolve {server}"),
+            SntpError::Timeout => write!(f, "no response within {} seconds", TIMEOUT.as_secs()),
+            SntpError::InvalidResponse(reason) => write!(f, "invalid response: {reason}"),
+            SntpError::KissOfDeath(code) => write!(f, "the server declined with {code}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for SntpError {
+    fn from(e: std::io::Error) -> Self {
+        SntpError::Io(e)
+    }
+}
+
+/// Queries `server`, a host name or address with an optional port, once.
+pub async fn query(server: &str) -> Result<SntpSample, SntpError> {
+    let address = resolve(server).await?;
+    let socket = UdpSocket::bind(if address.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    })
+    .await?;
+    socket.connect(address).await?;
+
+    let mut request = [0_u8; PACKET_LENGTH];
+    request[0] = CLIENT_HEADER;
+    let sent_at = Utc::now();
+    // Echoed back as the originate timestamp, which ties the response to this request
+    request[40..48].copy_from_slice(&to_ntp_timestamp(sent_at).to_be_bytes());
+    socket.send(&request).await?;
+
+    let mut response = [0_u8; PACKET_LENGTH];
+    let received_length = tokio::time::timeout(TIMEOUT, socket.recv(&mut response))
+        .await
+        .map_err(|_| SntpError::Timeout)??;
+    let received_at = Utc::now();
+
+    if received_length < PACKET_LENGTH {
+        return Err(SntpError::InvalidResponse("too short"));
+    }
+
+    parse_response(&response, &request[40..48], sent_at, received_at)
+}
+
+async fn resolve(server: &str) -> Result<SocketAddr, SntpError> {
+    let server = server.trim();
+    let address = match tokio::net::lookup_host(server).await {
+        Ok(mut addresses) => addresses.next(),
+        // No port given, so the default one
+        Err(_) => tokio::net::lookup_host((server, DEFAULT_PORT))
+            .await?
+            .next(),
+    };
+
+    address.ok_or_else(|| SntpError::Unresolved(server.to_owned()))
+}
+
+fn parse_response(
+    response: &[u8; PACKET_LENGTH],
+    transmit_timestamp: &[u8],
+    sent_at: DateTime<Utc>,
+    received_at: DateTime<Utc>,
+) -> Result<SntpSample, SntpError> {
+    let mode = response[0] & 0b111;
+    let stratum = response[1];
+
+    if mode != SERVER_MODE {
+        return Err(SntpError::InvalidResponse("not a server response"));
+    }
+
+    if response[24..32] != *transmit_timestamp {
+        return Err(SntpError::InvalidResponse("not a response to this request"));
+    }
+
+    if stratum == 0 {
+        return Err(SntpError::KissOfDeath(
+            String::from_utf8_lossy(&response[12..16]).into_owned(),
+        ));
+    }
+
+    let server_received_at = from_ntp_timestamp(read_u64(&response[32..40]))
+        .ok_or(SntpError::InvalidResponse("receive timestamp out of range"))?;
+    let server_sent_at = from_ntp_timestamp(read_u64(&response[40..48])).ok_or(
+        SntpError::InvalidResponse("transmit timestamp out of range"),
+    )?;
+
+    Ok(SntpSample {
+        offset: ((server_received_at - sent_at) + (server_sent_at - received_at)) / 2,
+        round_trip_delay: (received_at - sent_at) - (server_sent_at - server_received_at),
+        stratum,
+    })
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte))
+}
+
+/// Seconds since 1900 in the high 32 bits, the fraction of a second in the low ones.
+fn to_ntp_timestamp(date_time: DateTime<Utc>) -> u64 {
+    let seconds = u64::try_from(date_time.timestamp() + NTP_TO_UNIX_SECONDS).unwrap_or_default();
+    let fraction = (u64::from(date_time.timestamp_subsec_nanos()) << 32) / 1_000_000_000;
+
+    (seconds << 32) | fraction
+}
+
+/// Only the current NTP era, which lasts until 2036.
+fn from_ntp_timestamp(timestamp: u64) -> Option<DateTime<Utc>> {
+    let seconds = i64::try_from(timestamp >> 32).ok()? - NTP_TO_UNIX_SECONDS;
+    let nanos = ((timestamp & 0xFFFF_FFFF) * 1_000_000_000) >> 32;
+
+    DateTime::from_timestamp(seconds, u32::try_from(nanos).ok()?)
+}
+
+/// `+12.3 ms`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_millis(delta: TimeDelta) -> String {
+    let micros = delta.num_microseconds().unwrap_or(i64::MAX);
+
+    format!("{:+.1} ms", micros as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, Utc};
+    use tokio::net::UdpSocket;
+
+    use super::{from_ntp_timestamp, query, read_u64, to_ntp_timestamp, SntpError};
+
+    /// Answers one request as a server whose clock is `skew` ahead, holding on to
+    /// it for `processing`.
+    async fn responder(
+        skew: TimeDelta,
+        processing: TimeDelta,
+        stratum: u8,
+    ) -> (String, tokio::task::JoinHandle<()>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+
+        let handle = tokio::spawn(async move {
+            let mut request = [0_u8; 48];
+            let (_, client) = socket.recv_from(&mut request).await.unwrap();
+            let received_at = Utc::now() + skew;
+            tokio::time::sleep(processing.to_std().unwrap()).await;
+
+            let mut response = [0_u8; 48];
+            response[0] = 0b00_100_100;
+            response[1] = stratum;
+            response[12..16].copy_from_slice(b"RATE");
+            response[24..32].copy_from_slice(&request[40..48]);
+            response[32..40].copy_from_slice(&to_ntp_timestamp(received_at).to_be_bytes());
+            response[40..48].copy_from_slice(&to_ntp_timestamp(Utc::now() + skew).to_be_bytes());
+
+            socket.send_to(&response, client).await.unwrap();
+        });
+
+        (address, handle)
+    }
+
+    #[test]
+    fn ntp_timestamps_round_trip() {
+        let now = Utc::now();
+        let round_tripped = from_ntp_timestamp(to_ntp_timestamp(now)).unwrap();
+
+        assert!((round_tripped - now).abs() < TimeDelta::microseconds(1));
+        assert_eq!(read_u64(&[0, 0, 0, 0, 0, 0, 1, 2]), 258);
+    }
+
+    #[tokio::test]
+    async fn measures_the_offset_of_a_skewed_server() {
+        let (address, handle) = responder(
+            TimeDelta::milliseconds(1500),
+            TimeDelta::milliseconds(50),
+            2,
+        )
+        .await;
+
+        let sample = query(&address).await.unwrap();
+        handle.await.unwrap();
+
+        assert!(
+            (sample.offset - TimeDelta::milliseconds(1500)).abs() < TimeDelta::milliseconds(20)
+        );
+        // The server's processing time isn't network time
+        assert!(sample.round_trip_delay < TimeDelta::milliseconds(20));
+        assert_eq!(sample.stratum, 2);
+        assert!(sample.exceeds(100));
+        assert!(!sample.exceeds(2000));
+    }
+
+    #[tokio::test]
+    async fn reports_kiss_of_death() {
+        let (address, handle) = responder(TimeDelta::zero(), TimeDelta::zero(), 0).await;
+
+        let error = query(&address).await.unwrap_err();
+        handle.await.unwrap();
+
+        assert!(matches!(error, SntpError::KissOfDeath(code) if code == "RATE"));
+    }
+}
@@ -0,0 +1,212 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// How often the stopwatch redraws while running, fast enough for the milliseconds
+/// to look alive.
+pub const TICK: Duration = Duration::from_millis(31);
+
+/// A stopwatch on the monotonic clock, so wall-clock adjustments don't skew it.
+/// Every method takes the current `Instant` instead of reading it.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch {
+    started_at: Option<Instant>,
+    /// Time run before the last start.
+    accumulated: Duration,
+    /// Elapsed time at each lap, oldest first.
+    splits: Vec<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lap {
+    pub number: usize,
+    pub lap_time: Duration,
+    pub split_time: Duration,
+}
+
+#[derive(Serialize)]
+struct LapRecord {
+    lap: usize,
+    lap_time: String,
+    split_time: String,
+}
+
+impl Stopwatch {
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        self.started_at.get_or_insert(now);
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        if let Some(started_at) = self.started_at.take() {
+            self.accumulated += now.saturating_duration_since(started_at);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn lap(&mut self, now: Instant) {
+        if self.is_running() {
+            self.splits.push(self.elapsed(now));
+        }
+    }
+
+    #[must_use]
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.accumulated
+            + self.started_at.map_or(Duration::ZERO, |started_at| {
+                now.saturating_duration_since(started_at)
+            })
+    }
+
+    /// Newest first, the way the list shows them.
+    #[must_use]
+    pub fn laps(&self) -> Vec<Lap> {
+        let mut previous_split = Duration::ZERO;
+        let mut laps: Vec<Lap> = self
+            .splits
+            .iter()
+            .enumerate()
+            .map(|(index, split_time)| {
+                let lap = Lap {
+                    number: index + 1,
+                    lap_time: split_time.saturating_sub(previous_split),
+                    split_time: *split_time,
+                };
+                previous_split = *split_time;
+                lap
+            })
+            .collect();
+
+        laps.reverse();
+        laps
+    }
+
+    /// The laps oldest first, one row each.
+    pub fn laps_csv(&self) -> Result<String, csv::Error> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        for lap in self.laps().into_iter().rev() {
+            writer.serialize(LapRecord {
+                lap: lap.number,
+                lap_time: format_duration(lap.lap_time),
+                split_time: format_duration(lap.split_time),
+            })?;
+        }
+
+        let csv = writer
+            .into_inner()
+            .map_err(|e| csv::Error::from(e.into_error()))?;
+
+        Ok(String::from_utf8_lossy(&csv).into_owned())
+    }
+}
+
+/// `01:02:03.456`
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+        duration.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{format_duration, Lap, Stopwatch};
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn adds_up_the_time_run_between_pauses() {
+        let start = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        assert_eq!(stopwatch.elapsed(start), Duration::ZERO);
+
+        stopwatch.start(start);
+        // Starting again doesn't restart it.
+        stopwatch.start(start + millis(500));
+        assert_eq!(stopwatch.elapsed(start + millis(1500)), millis(1500));
+
+        stopwatch.pause(start + millis(2000));
+        assert!(!stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(start + millis(9000)), millis(2000));
+
+        stopwatch.start(start + millis(10_000));
+        assert_eq!(stopwatch.elapsed(start + millis(10_250)), millis(2250));
+
+        stopwatch.reset();
+        assert!(!stopwatch.is_running());
+        assert_eq!(stopwatch.elapsed(start + millis(11_000)), Duration::ZERO);
+    }
+
+    #[test]
+    fn laps_newest_first_with_their_own_time() {
+        let start = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+
+        // Not running yet, so no lap.
+        stopwatch.lap(start);
+        stopwatch.start(start);
+        stopwatch.lap(start + millis(1200));
+        stopwatch.pause(start + millis(2000));
+        stopwatch.lap(start + millis(5000));
+        stopwatch.start(start + millis(6000));
+        stopwatch.lap(start + millis(6500));
+
+        assert_eq!(
+            stopwatch.laps(),
+            [
+                Lap {
+                    number: 2,
+                    lap_time: millis(1300),
+                    split_time: millis(2500),
+                },
+                Lap {
+                    number: 1,
+                    lap_time: millis(1200),
+                    split_time: millis(1200),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn exports_laps_oldest_first() {
+        let start = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        assert_eq!(stopwatch.laps_csv().unwrap(), "");
+
+        stopwatch.start(start);
+        stopwatch.lap(start + millis(61_005));
+        stopwatch.lap(start + millis(3_723_456));
+
+        assert_eq!(
+            stopwatch.laps_csv().unwrap(),
+            "lap,lap_time,split_time\n\
+             1,00:01:01.005,00:01:01.005\n\
+             2,01:01:02.451,01:02:03.456\n"
+        );
+    }
+
+    #[test]
+    fn formats_hours_to_milliseconds() {
+        assert_eq!(format_duration(Duration::ZERO), "00:00:00.000");
+        assert_eq!(format_duration(millis(3_723_456)), "01:02:03.456");
+    }
+}
@@ -0,0 +1,258 @@
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The Julian date of 2000-01-01 12:00 UTC, the J2000 epoch.
+const J2000: f64 = 2_451_545.0;
+/// The Julian date of the Unix epoch.
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2_440_587.5;
+const AXIAL_TILT_DEGREES: f64 = 23.4397;
+/// Where the Sun's upper limb touches the horizon, allowing for refraction.
+const SUNRISE_ELEVATION_DEGREES: f64 = -0.833;
+const CIVIL_TWILIGHT_ELEVATION_DEGREES: f64 = -6.0;
+const MICRODEGREES: f64 = 1_000_000.0;
+
+/// A place on Earth, in millionths of a degree so that `UniConfig` stays `Eq`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub latitude_microdegrees: i32,
+    /// East of Greenwich is positive.
+    pub longitude_microdegrees: i32,
+}
+
+impl Coordinates {
+    /// Decimal degrees, such as `21.0285` and `105.8542`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn parse(latitude: &str, longitude: &str) -> Option<Self> {
+        let latitude = latitude.trim().parse::<f64>().ok()?;
+        let longitude = longitude.trim().parse::<f64>().ok()?;
+
+        // Also rejects NaN
+        if !((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)) {
+            return None;
+        }
+
+        Some(Self {
+            latitude_microdegrees: (latitude * MICRODEGREES).round() as i32,
+            longitude_microdegrees: (longitude * MICRODEGREES).round() as i32,
+        })
+    }
+
+    #[must_use]
+    pub fn latitude(self) -> f64 {
+        f64::from(self.latitude_microdegrees) / MICRODEGREES
+    }
+
+    #[must_use]
+    pub fn longitude(self) -> f64 {
+        f64::from(self.longitude_microdegrees) / MICRODEGREES
+    }
+}
+
+/// When the Sun passes an elevation on its way up and on its way down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossing {
+    Times {
+        rise: DateTime<Utc>,
+        set: DateTime<Utc>,
+    },
+    /// Midnight sun, or for twilight, white nights.
+    AlwaysAbove,
+    /// Polar night.
+    AlwaysBelow,
+}
+
+impl Crossing {
+    /// How long the Sun stays above the elevation.
+    #[must_use]
+    pub fn duration(&self) -> TimeDelta {
+        match self {
+            Crossing::Times { rise, set } => *set - *rise,
+            Crossing::AlwaysAbove => TimeDelta::days(1),
+            Crossing::AlwaysBelow => TimeDelta::zero(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SunDay {
+    pub solar_noon: DateTime<Utc>,
+    pub sunrise_sunset: Crossing,
+    /// Civil dawn and dusk, when the Sun is 6° below the horizon.
+    pub civil_twilight: Crossing,
+}
+
+impl SunDay {
+    #[must_use]
+    pub fn day_length(&self) -> TimeDelta {
+        self.sunrise_sunset.duration()
+    }
+}
+
+/// The Sun's day at `coordinates` around the local solar noon of `date`, from the
+/// sunrise equation, which is good to about a minute away from the poles.
+#[must_use]
+pub fn sun_day(date: NaiveDate, coordinates: Coordinates) -> Option<SunDay> {
+    let days_since_j2000 = (date - NaiveDate::from_ymd_opt(2000, 1, 1)?).num_days();
+    #[allow(clippy::cast_precision_loss)]
+    let mean_solar_time = days_since_j2000 as f64 - coordinates.longitude() / 360.0;
+
+    let mean_anomaly = (357.5291 + 0.985_600_28 * mean_solar_time).rem_euclid(360.0);
+    let mean_anomaly_radians = mean_anomaly.to_radians();
+    let equation_of_center = 1.9148 * mean_anomaly_radians.sin()
+        + 0.0200 * (2.0 * mean_anomaly_radians).sin()
+        + 0.0003 * (3.0 * mean_anomaly_radians).sin();
+    let ecliptic_longitude =
+        (mean_anomaly + equation_of_center + 180.0 + 102.9372).rem_euclid(360.0);
+    let ecliptic_longitude_radians = ecliptic_longitude.to_radians();
+
+    let transit = J2000 + mean_solar_time + 0.0053 * mean_anomaly_radians.sin()
+        - 0.0069 * (2.0 * ecliptic_longitude_radians).sin();
+    let declination_sin = ecliptic_longitude_radians.sin() * AXIAL_TILT_DEGREES.to_radians().sin();
+    let declination = declination_sin.asin();
+    let latitude = coordinates.latitude().to_radians();
+
+    let crossing = |elevation_degrees: f64| {
+        let hour_angle_cos = (elevation_degrees.to_radians().sin()
+            - latitude.sin() * declination_sin)
+            / (latitude.cos() * declination.cos());
+
+        if hour_angle_cos < -1.0 {
+            Some(Crossing::AlwaysAbove)
+        } else if hour_angle_cos > 1.0 {
+            Some(Crossing::AlwaysBelow)
+        } else {
+            let half_day = hour_angle_cos.acos().to_degrees() / 360.0;
+
+            Some(Crossing::Times {
+                rise: from_julian_date(transit - half_day)?,
+                set: from_julian_date(transit + half_day)?,
+            })
+        }
+    };
+
+    Some(SunDay {
+        solar_noon: from_julian_date(transit)?,
+        sunrise_sunset: crossing(SUNRISE_ELEVATION_DEGREES)?,
+        civil_twilight: crossing(CIVIL_TWILIGHT_ELEVATION_DEGREES)?,
+    })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_julian_date(julian_date: f64) -> Option<DateTime<Utc>> {
+    let millis = ((julian_date - UNIX_EPOCH_JULIAN_DATE) * 86_400_000.0).round();
+
+    DateTime::from_timestamp_millis(millis as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, TimeDelta, TimeZone, Utc};
+
+    use super::{sun_day, Coordinates, Crossing};
+
+    const LONDON: (&str, &str) = ("51.5074", "-0.1278");
+    const NEW_YORK: (&str, &str) = ("40.7128", "-74.0060");
+    const TROMSO: (&str, &str) = ("69.6492", "18.9553");
+
+    fn coordinates((latitude, longitude): (&str, &str)) -> Coordinates {
+        Coordinates::parse(latitude, longitude).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    /// Within two minutes of the reference, which is rounded to the minute.
+    fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>) {
+        assert!(
+            (actual - expected).abs() <= TimeDelta::minutes(2),
+            "{actual} isn't near {expected}"
+        );
+    }
+
+    fn assert_times(crossing: Crossing, rise: DateTime<Utc>, set: DateTime<Utc>) {
+        let Crossing::Times {
+            rise: actual_rise,
+            set: actual_set,
+        } = crossing
+        else {
+            panic!("{crossing:?} has no times");
+        };
+
+        assert_near(actual_rise, rise);
+        assert_near(actual_set, set);
+    }
+
+    // Reference values are from the NOAA Solar Calculator, converted to UTC
+
+    #[test]
+    fn london_midsummer() {
+        let sun_day = sun_day(date(2024, 6, 21), coordinates(LONDON)).unwrap();
+
+        assert_near(sun_day.solar_noon, utc(2024, 6, 21, 12, 2));
+        assert_times(
+            sun_day.sunrise_sunset,
+            utc(2024, 6, 21, 3, 43),
+            utc(2024, 6, 21, 20, 21),
+        );
+        assert_times(
+            sun_day.civil_twilight,
+            utc(2024, 6, 21, 2, 55),
+            utc(2024, 6, 21, 21, 9),
+        );
+        assert!(
+            (sun_day.day_length() - TimeDelta::minutes(16 * 60 + 38)).abs()
+                <= TimeDelta::minutes(3)
+        );
+    }
+
+    #[test]
+    fn new_york_midwinter() {
+        let sun_day = sun_day(date(2024, 12, 21), coordinates(NEW_YORK)).unwrap();
+
+        assert_near(sun_day.solar_noon, utc(2024, 12, 21, 16, 54));
+        assert_times(
+            sun_day.sunrise_sunset,
+            utc(2024, 12, 21, 12, 16),
+            utc(2024, 12, 21, 21, 32),
+        );
+        assert_times(
+            sun_day.civil_twilight,
+            utc(2024, 12, 21, 11, 46),
+            utc(2024, 12, 21, 22, 2),
+        );
+    }
+
+    #[test]
+    fn polar_day_and_night() {
+        let midsummer = sun_day(date(2024, 6, 21), coordinates(TROMSO)).unwrap();
+        let midwinter = sun_day(date(2024, 12, 21), coordinates(TROMSO)).unwrap();
+
+        assert_eq!(midsummer.sunrise_sunset, Crossing::AlwaysAbove);
+        assert_eq!(midsummer.civil_twilight, Crossing::AlwaysAbove);
+        assert_eq!(midsummer.day_length(), TimeDelta::days(1));
+
+        assert_eq!(midwinter.sunrise_sunset, Crossing::AlwaysBelow);
+        assert_eq!(midwinter.day_length(), TimeDelta::zero());
+        // The Sun stays within 6° of the horizon around noon
+        assert!(matches!(midwinter.civil_twilight, Crossing::Times { .. }));
+    }
+
+    #[test]
+    fn parses_coordinates() {
+        let hanoi = Coordinates::parse(" 21.0285", "105.8542 ").unwrap();
+
+        assert_eq!(hanoi.latitude_microdegrees, 21_028_500);
+        assert!((hanoi.longitude() - 105.8542).abs() < 1e-9);
+        assert_eq!(Coordinates::parse("91", "0"), None);
+        assert_eq!(Coordinates::parse("0", "-180.5"), None);
+        assert_eq!(Coordinates::parse("NaN", "0"), None);
+        assert_eq!(Coordinates::parse("north", "0"), None);
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+/// `99:59:59`, the longest duration [`parse_duration`] accepts.
+pub const MAX_DURATION_SECS: u64 = 100 * 3600 - 1;
+
+/// A named countdown, persisted in `UniConfig` so a running one survives restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountdownTimer {
+    pub name: String,
+    /// What the timer counts down from when started afresh.
+    pub duration_secs: u64,
+    pub state: TimerState,
+    pub play_sound: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimerState {
+    #[default]
+    Idle,
+    /// The end time rather than what's left, so the remaining time is recomputed on
+    /// every tick and after a restart.
+    Running {
+        ends_at: DateTime<Utc>,
+    },
+    Paused {
+        remaining_secs: u64,
+    },
+    Finished,
+}
+
+impl CountdownTimer {
+    #[must_use]
+    pub fn new(name: String, duration_secs: u64, play_sound: bool) -> Self {
+        Self {
+            name,
+            duration_secs,
+            state: TimerState::Idle,
+            play_sound,
+        }
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, TimerState::Running { .. })
+    }
+
+    #[must_use]
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> u64 {
+        match self.state {
+            TimerState::Idle => self.duration_secs,
+            TimerState::Running { ends_at } => {
+                // Rounded up, so a timer reads 00:01 until it's actually over
+                u64::try_from((ends_at - now).num_milliseconds())
+                    .unwrap_or_default()
+                    .div_ceil(1000)
+            }
+            TimerState::Paused { remaining_secs } => remaining_secs,
+            TimerState::Finished => 0,
+        }
+    }
+
+    pub fn start(&mut self, now: DateTime<Utc>) {
+        let remaining_secs = match self.state {
+            TimerState::Running { .. } => return,
+            TimerState::Paused { remaining_secs } => remaining_secs,
+            TimerState::Idle | TimerState::Finished => self.duration_secs,
+        };
+
+        self.state = TimerState::Running {
+            ends_at: now + TimeDelta::seconds(i64::try_from(remaining_secs).unwrap_or_default()),
+        };
+    }
+
+    pub fn pause(&mut self, now: DateTime<Utc>) {
+        if self.is_running() {
+            self.state = TimerState::Paused {
+                remaining_secs: self.remaining_secs(now),
+            };
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = TimerState::Idle;
+    }
+
+    /// Marks the timer finished if it ran out by `now`, returning whether it did.
+    pub fn finish_if_due(&mut self, now: DateTime<Utc>) -> bool {
+        match self.state {
+            TimerState::Running { ends_at } if ends_at <= now => {
+                self.state = TimerState::Finished;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Reads `90` as minutes, `4:30` as minutes and seconds and `1:15:00` as hours,
+/// minutes and seconds.
+#[must_use]
+pub fn parse_duration(input: &str) -> Option<u64> {
+    let parts = input
+        .trim()
+        .split(':')
+        .map(|part| part.trim().parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+
+    let duration_secs = match parts.as_slice() {
+        [minutes] => minutes.checked_mul(60)?,
+        [minutes, seconds] if *seconds < 60 => minutes.checked_mul(60)? + seconds,
+        [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => {
+            hours.checked_mul(3600)? + minutes * 60 + seconds
+        }
+        _ => return None,
+    };
+
+    (1..=MAX_DURATION_SECS)
+        .contains(&duration_secs)
+        .then_some(duration_secs)
+}
+
+/// `04:30`, or `1:15:00` from an hour up.
+#[must_use]
+pub fn format_remaining(seconds: u64) -> String {
+    match seconds / 3600 {
+        0 => format!("{:02}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{hours}:{:02}:{:02}", seconds % 3600 / 60, seconds % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+
+    use super::{format_remaining, parse_duration, CountdownTimer, TimerState, MAX_DURATION_SECS};
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap()
+    }
+
+    fn millis_after(start: DateTime<Utc>, millis: i64) -> DateTime<Utc> {
+        start + TimeDelta::milliseconds(millis)
+    }
+
+    fn tea() -> CountdownTimer {
+        CountdownTimer::new("Tea".to_owned(), 240, true)
+    }
+
+    #[test]
+    fn rounds_the_remaining_time_up() {
+        let mut timer = tea();
+        timer.start(start());
+
+        assert_eq!(timer.remaining_secs(start()), 240);
+        assert_eq!(timer.remaining_secs(millis_after(start(), 1)), 240);
+        assert_eq!(timer.remaining_secs(millis_after(start(), 1000)), 239);
+        assert_eq!(timer.remaining_secs(millis_after(start(), 239_001)), 1);
+        assert_eq!(timer.remaining_secs(millis_after(start(), 240_000)), 0);
+        // Past the end, before the next tick finishes it
+        assert_eq!(timer.remaining_secs(millis_after(start(), 245_000)), 0);
+    }
+
+    #[test]
+    fn pausing_keeps_what_was_left() {
+        let mut timer = tea();
+        assert_eq!(timer.remaining_secs(start()), 240);
+
+        timer.start(start());
+        timer.pause(millis_after(start(), 60_500));
+        assert_eq!(
+            timer.state,
+            TimerState::Paused {
+                remaining_secs: 180
+            }
+        );
+        assert_eq!(timer.remaining_secs(millis_after(start(), 600_000)), 180);
+
+        // Pausing twice changes nothing, resuming counts down from there.
+        timer.pause(millis_after(start(), 120_000));
+        timer.start(millis_after(start(), 600_000));
+        assert_eq!(
+            timer.state,
+            TimerState::Running {
+                ends_at: millis_after(start(), 780_000)
+            }
+        );
+
+        // Starting a running timer doesn't restart it.
+        timer.start(millis_after(start(), 700_000));
+        assert_eq!(timer.remaining_secs(millis_after(start(), 700_000)), 80);
+    }
+
+    #[test]
+    fn finishes_once_it_runs_out() {
+        let mut timer = tea();
+        assert!(!timer.finish_if_due(millis_after(start(), 240_000)));
+
+        timer.start(start());
+        assert!(!timer.finish_if_due(millis_after(start(), 239_999)));
+        assert!(timer.finish_if_due(millis_after(start(), 240_000)));
+        assert_eq!(timer.state, TimerState::Finished);
+        assert!(!timer.finish_if_due(millis_after(start(), 241_000)));
+
+        // A finished timer starts over from its full duration.
+        timer.start(millis_after(start(), 300_000));
+        assert_eq!(timer.remaining_secs(millis_after(start(), 300_000)), 240);
+
+        timer.reset();
+        assert_eq!(timer.state, TimerState::Idle);
+    }
+
+    #[test]
+    fn keeps_running_across_a_restart() {
+        let mut timer = tea();
+        timer.start(start());
+
+        let saved = serde_json::to_string(&timer).unwrap();
+        let mut restored: CountdownTimer = serde_json::from_str(&saved).unwrap();
+
+        assert_eq!(restored, timer);
+        assert_eq!(restored.remaining_secs(millis_after(start(), 100_000)), 140);
+        // Closed for longer than it had left, it finishes on the first tick.
+        assert!(restored.finish_if_due(millis_after(start(), 3_600_000)));
+    }
+
+    #[test]
+    fn parses_minutes_seconds_and_hours() {
+        assert_eq!(parse_duration("90"), Some(5400));
+        assert_eq!(parse_duration(" 4 : 30 "), Some(270));
+        assert_eq!(parse_duration("1:15:00"), Some(4500));
+        assert_eq!(parse_duration("0:01"), Some(1));
+        assert_eq!(parse_duration("99:59:59"), Some(MAX_DURATION_SECS));
+
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("4:60"), None);
+        assert_eq!(parse_duration("1:60:00"), None);
+        assert_eq!(parse_duration("100:00:00"), None);
+        assert_eq!(parse_duration("6000"), None);
+        assert_eq!(parse_duration("1:2:3:4"), None);
+        assert_eq!(parse_duration("-5"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("18446744073709551615"), None);
+    }
+
+    #[test]
+    fn formats_hours_only_when_there_are_some() {
+        assert_eq!(format_remaining(270), "04:30");
+        assert_eq!(format_remaining(3599), "59:59");
+        assert_eq!(format_remaining(4500), "1:15:00");
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Datelike, SecondsFormat, TimeZone, Utc};
+
+/// Epoch values this large or larger are read as milliseconds: as seconds they'd be
+/// past the year 5000.
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampFormat {
+    EpochSeconds,
+    EpochMillis,
+    Rfc3339,
+    Rfc2822,
+}
+
+/// A way of writing an instant, as the converter lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Representation {
+    EpochSeconds,
+    EpochMillis,
+    Rfc3339Utc,
+    Rfc3339Local,
+    Rfc2822Utc,
+    Rfc2822Local,
+}
+
+impl Representation {
+    pub const ALL: [Self; 6] = [
+        Self::EpochSeconds,
+        Self::EpochMillis,
+        Self::Rfc3339Utc,
+        Self::Rfc3339Local,
+        Self::Rfc2822Utc,
+        Self::Rfc2822Local,
+    ];
+
+    /// `None` when this representation can't write `instant`.
+    #[must_use]
+    pub fn format(self, instant: DateTime<Utc>) -> Option<String> {
+        self.format_with_local(instant, &chrono::Local)
+    }
+
+    /// Like [`Representation::format`], with `local` standing in for the system's zone.
+    #[must_use]
+    pub fn format_with_local<Tz: TimeZone>(
+        self,
+        instant: DateTime<Utc>,
+        local: &Tz,
+    ) -> Option<String>
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        match self {
+            Self::EpochSeconds => Some(instant.timestamp().to_string()),
+            Self::EpochMillis => Some(instant.timestamp_millis().to_string()),
+            Self::Rfc3339Utc => Some(instant.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+            Self::Rfc3339Local => Some(
+                instant
+                    .with_timezone(local)
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            ),
+            Self::Rfc2822Utc => to_rfc2822(&instant),
+            Self::Rfc2822Local => to_rfc2822(&instant.with_timezone(local)),
+        }
+    }
+}
+
+/// chrono panics writing RFC 2822 dates outside four-digit years, which epochs reach
+/// easily.
+fn to_rfc2822<Tz: TimeZone>(date_time: &DateTime<Tz>) -> Option<String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    (0..=9999)
+        .contains(&date_time.year())
+        .then(|| date_time.to_rfc2822())
+}
+
+/// Works out which of the supported formats `input` is in, and the instant it names.
+#[must_use]
+pub fn detect(input: &str) -> Option<(TimestampFormat, DateTime<Utc>)> {
+    let input = input.trim();
+
+    if let Some(epoch) = parse_epoch(input) {
+        return Some(epoch);
+    }
+
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(input) {
+        return Some((TimestampFormat::Rfc3339, date_time.to_utc()));
+    }
+
+    DateTime::parse_from_rfc2822(input)
+        .ok()
+        .map(|date_time| (TimestampFormat::Rfc2822, date_time.to_utc()))
+}
+
+/// Whole seconds or milliseconds, or seconds with a fraction such as `1700000000.5`.
+fn parse_epoch(input: &str) -> Option<(TimestampFormat, DateTime<Utc>)> {
+    let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+    let digits = whole.strip_prefix('-').unwrap_or(whole);
+
+    if digits.is_empty()
+        || !digits.bytes().all(|byte| byte.is_ascii_digit())
+        || !fraction.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let value = whole.parse::<i64>().ok()?;
+    // `-0.5` parses its whole part as 0, so the sign is read from the input
+    let is_negative = whole.starts_with('-');
+
+    if value.abs() >= MILLIS_THRESHOLD && fraction.is_empty() {
+        return DateTime::from_timestamp_millis(value)
+            .map(|instant| (TimestampFormat::EpochMillis, instant));
+    }
+
+    // Nanoseconds from the first nine digits of the fraction
+    let nanos = format!("{fraction:0<9}")[..9].parse::<u32>().ok()?;
+    let instant = if is_negative && nanos > 0 {
+        DateTime::from_timestamp(value - 1, 1_000_000_000 - nanos)
+    } else {
+        DateTime::from_timestamp(value, nanos)
+    }?;
+
+    Some((TimestampFormat::EpochSeconds, instant))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    use super::{detect, Representation, TimestampFormat};
+
+    #[test]
+    fn detects_epochs() {
+        let instant = Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap();
+
+        assert_eq!(
+            detect("1700000000"),
+            Some((TimestampFormat::EpochSeconds, instant))
+        );
+        assert_eq!(
+            detect(" 1700000000000 "),
+            Some((TimestampFormat::EpochMillis, instant))
+        );
+        assert_eq!(
+            detect("1700000000.25").map(|(_, instant)| instant.timestamp_millis()),
+            Some(1_700_000_000_250)
+        );
+        assert_eq!(
+            detect("-1.5").map(|(_, instant)| instant.timestamp_millis()),
+            Some(-1500)
+        );
+        assert_eq!(
+            detect("-0.5").map(|(_, instant)| instant.timestamp_millis()),
+            Some(-500)
+        );
+
+        // The year 10000
+        let (format, instant) = detect("253402300800000").unwrap();
+        assert_eq!(format, TimestampFormat::EpochMillis);
+        assert_eq!(
+            Representation::Rfc3339Utc.format_with_local(instant, &Utc),
+            Some("+10000-01-01T00:00:00Z".to_owned())
+        );
+        assert_eq!(
+            Representation::Rfc2822Utc.format_with_local(instant, &Utc),
+            None
+        );
+
+        assert_eq!(detect("17e9"), None);
+        assert_eq!(detect("-"), None);
+    }
+
+    #[test]
+    fn detects_rfc_formats() {
+        let instant = Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap();
+
+        assert_eq!(
+            detect("2023-11-15T05:13:20+07:00"),
+            Some((TimestampFormat::Rfc3339, instant))
+        );
+        assert_eq!(
+            detect("Tue, 14 Nov 2023 22:13:20 +0000"),
+            Some((TimestampFormat::Rfc2822, instant))
+        );
+        assert_eq!(detect("yesterday"), None);
+    }
+
+    #[test]
+    fn formats_every_representation() {
+        let instant = Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap();
+        let local = FixedOffset::east_opt(7 * 3600).unwrap();
+        let formatted: Vec<String> = Representation::ALL
+            .into_iter()
+            .filter_map(|representation| representation.format_with_local(instant, &local))
+            .collect();
+
+        assert_eq!(
+            formatted,
+            [
+                "1700000000",
+                "1700000000000",
+                "2023-11-14T22:13:20Z",
+                "2023-11-15T05:13:20+07:00",
+                "Tue, 14 Nov 2023 22:13:20 +0000",
+                "Wed, 15 Nov 2023 05:13:20 +0700",
+            ]
+        );
+    }
+}
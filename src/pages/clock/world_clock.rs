@@ -0,0 +1,80 @@
+use chrono::{DateTime, NaiveDate, Offset, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// How many zones the add form suggests at once.
+pub const MAX_SUGGESTIONS: usize = 8;
+
+/// A world clock zone as of one instant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneTime {
+    pub tz: Tz,
+    pub city: String,
+    pub time: DateTime<Tz>,
+    pub utc_offset: String,
+    /// Days ahead of the local date, negative when behind.
+    pub day_difference: i64,
+}
+
+impl ZoneTime {
+    #[must_use]
+    pub fn new(tz: Tz, now: DateTime<Utc>, local_date: NaiveDate) -> Self {
+        let time = now.with_timezone(&tz);
+
+        Self {
+            tz,
+            city: city(tz),
+            utc_offset: format_utc_offset(time.offset().fix().local_minus_utc()),
+            day_difference: (time.date_naive() - local_date).num_days(),
+            time,
+        }
+    }
+}
+
+/// `America/Argentina/Buenos_Aires` reads as `Buenos Aires`.
+#[must_use]
+pub fn city(tz: Tz) -> String {
+    tz.name()
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .replace('_', " ")
+}
+
+/// `UTC`, `UTC+7` or `UTC-3:30`.
+#[must_use]
+pub fn format_utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let hours = seconds.abs() / 3600;
+    let minutes = seconds.abs() % 3600 / 60;
+
+    match (hours, minutes) {
+        (0, 0) => "UTC".to_owned(),
+        (hours, 0) => format!("UTC{sign}{hours}"),
+        (hours, minutes) => format!("UTC{sign}{hours}:{minutes:02}"),
+    }
+}
+
+/// The zones whose name contains every word of `query`, cities first, skipping
+/// `already_added`.
+#[must_use]
+pub fn search_zones(query: &str, already_added: &[String]) -> Vec<Tz> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+    if words.is_empty() {
+        return vec![];
+    }
+
+    let mut matches: Vec<Tz> = TZ_VARIANTS
+        .iter()
+        .copied()
+        .filter(|tz| !already_added.iter().any(|name| name == tz.name()))
+        .filter(|tz| {
+            let name = tz.name().to_lowercase().replace('_', " ");
+            words.iter().all(|word| name.contains(word))
+        })
+        .collect();
+
+    matches.sort_by_key(|tz| (!city(*tz).to_lowercase().starts_with(&words[0]), tz.name()));
+    matches.truncate(MAX_SUGGESTIONS);
+    matches
+}
@@ -1,13 +1,39 @@
+mod benchmark;
+mod dca;
+mod demo;
+mod fees;
+mod filters;
+mod ledger;
+mod portfolio;
+mod prices;
+mod report;
+mod stablecoins;
+mod wallets;
+
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
 
 use crate::fl;
 use crate::{app, config, pages};
-use cosmic::iced::alignment::Vertical;
+use benchmark::{Benchmark, BenchmarkKind};
+use chrono::NaiveDate;
+use cosmic::iced::{alignment::Vertical, Length};
 use cosmic::widget::{icon, segmented_button};
-use cosmic::{cosmic_config, theme, widget, Apply, Element, Task};
-use serde::Deserialize;
+use cosmic::{cosmic_config, cosmic_theme, theme, widget, Apply, Element, Task};
+use dca::{DcaBuyStatus, DcaPlanForm, DcaPlanFormError, PriceScenario, ScheduledBuy};
+use filters::{PlatformFilter, ProfitFilter, StableCoinFilter};
+use ledger::{Journal, LedgerFormat};
+use portfolio::{CoinHolding, PortfolioSummary};
+use prices::{CoinMarketCapProvider, HistoricalPrices};
+use report::{Report, ReportFormat};
+use serde::{Deserialize, Serialize};
+use wallets::{BlockExplorer, WalletChain};
+
+pub use dca::{DcaFrequency, DcaPlan};
+pub use fees::FeeAllocation;
+pub use filters::HoldingsFilter;
+pub use wallets::WalletAddress;
 
 #[derive(Debug, Clone)]
 pub enum PaidEntriesPageMessage {
@@ -23,16 +49,76 @@ pub enum PaidEntriesPageMessage {
     CmcApiKeyClearInput,
     ToggleOnEditApiKey,
     SwitchTab(segmented_button::Entity),
+    DcaSymbolInput(String),
+    DcaAmountInput(String),
+    DcaCurrencyInput(String),
+    DcaFrequencySelected(usize),
+    DcaStartDateInput(String),
+    DcaPlanAdd,
+    DcaPlanRemove(usize),
+    HistoricalPricesLoad,
+    HistoricalPricesFetched(HistoricalPrices),
+    HistoricalPricesFetchingFailed(String),
+    LedgerExport(LedgerFormat),
+    LedgerExported(PathBuf, usize),
+    LedgerImport,
+    LedgerImported(RawJsonData),
+    LedgerFileFailed(String),
+    WalletChainSelected(usize),
+    WalletAddressInput(String),
+    WalletLabelInput(String),
+    WalletAdd,
+    WalletRemove(usize),
+    WalletBalancesRefresh,
+    WalletBalancesFetched(Vec<(WalletAddress, Result<f64, String>)>),
+    BitcoinExplorerUrlInput(String),
+    EvmExplorerUrlInput(String),
+    EvmExplorerApiKeyInput(String),
+    ExplorerSettingsSubmit,
+    ExcludeStableCoinsToggled(bool),
+    DepegBandInput(String),
+    HoldingsSearchInput(String),
+    HoldingsSearchSettled(String),
+    HoldingsPlatformSelected(usize),
+    HoldingsStableCoinFilterSelected(usize),
+    HoldingsProfitFilterSelected(usize),
+    HoldingsDustThresholdInput(String),
+    HoldingsGroupByPlatformToggled(bool),
+    FeeAllocationSelected(usize),
+    ReportExport,
+    ReportExported(PathBuf),
+    ReportExportFailed(String),
+    DemoModeToggled(bool),
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BuyEntry {
-    // #[serde(rename = "isStableCoin", default)]
-    // is_stable_coin: bool,
-    // date: String,
-    // #[serde(rename = "amountUsd")]
-    // amount_usd: f64,
-    // amount: f64,
+    /// `None` falls back to the built-in list of known stablecoins.
+    #[serde(
+        rename = "isStableCoin",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    is_stable_coin: Option<bool>,
+    date: String,
+    #[serde(rename = "amountUsd")]
+    amount_usd: f64,
+    amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fee: Option<fees::Fee>,
+}
+
+impl BuyEntry {
+    /// Entries are dated either as `2024-01-31` or as a full RFC 3339 timestamp.
+    fn date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.date, "%Y-%m-%d")
+            .ok()
+            .or_else(|| {
+                chrono::DateTime::parse_from_rfc3339(&self.date)
+                    .ok()
+                    .map(|date_time| date_time.date_naive())
+            })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -71,6 +157,9 @@ pub struct Usd {
 
 type RawJsonData = HashMap<String, Vec<BuyEntry>>;
 
+/// How long the holdings search has to stay unchanged before it's saved.
+const HOLDINGS_SEARCH_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
 fn parse_paid_entries(raw_json: &str) -> Result<RawJsonData, serde_json::Error> {
     let json_data: RawJsonData = serde_json::from_str(raw_json)?;
 
@@ -106,19 +195,46 @@ pub struct PaidEntriesPage {
     is_edit_api_key_unlocked: bool,
     editing_cmc_api_key: String,
     tab_model: segmented_button::SingleSelectModel,
+    dca_plan_form: DcaPlanForm,
+    dca_frequency_options: Vec<String>,
+    /// One per plan, worked out in update whenever the entries or the plans change.
+    dca_schedules: Vec<Vec<ScheduledBuy>>,
+    historical_prices: Option<HistoricalPrices>,
+    is_loading_historical_prices: bool,
+    /// Replayed in update whenever the entries, the quotes or the history change.
+    benchmarks: Vec<(BenchmarkKind, Option<Benchmark>)>,
+    wallet_form: WalletAddress,
+    wallet_chain_options: Vec<&'static str>,
+    wallet_balances: Option<Vec<(WalletAddress, Result<f64, String>)>>,
+    is_fetching_wallet_balances: bool,
+    editing_bitcoin_explorer_url: String,
+    editing_evm_explorer_url: String,
+    editing_evm_explorer_api_key: String,
+    editing_depeg_band: String,
+    editing_dust_threshold: String,
+    /// Filters as it's typed, and is saved once typing settles.
+    editing_holdings_search: String,
+    stable_coin_filter_options: Vec<String>,
+    profit_filter_options: Vec<String>,
+    /// `None` first, for "all platforms", then every platform of the fetched quotes.
+    platform_filters: Vec<Option<PlatformFilter>>,
+    platform_filter_options: Vec<String>,
+    fee_allocation_options: Vec<String>,
 }
 
 enum PaidEntriesPageTabs {
     CoinBalance,
     CoinPrices,
+    DcaPlans,
+    Performance,
+    Wallets,
 }
 
 impl PaidEntriesPage {
     #[must_use]
     pub fn new(config: config::UniConfig, config_handler: Option<cosmic_config::Config>) -> Self {
-        Self {
+        let mut page = Self {
             config_handler,
-            config,
             tab_model: segmented_button::SingleSelectModel::builder()
                 .insert(|it| {
                     it.text(fl!("tab-coin-balance"))
@@ -129,11 +245,737 @@ impl PaidEntriesPage {
                     it.text(fl!("tab-coin-prices"))
                         .data(PaidEntriesPageTabs::CoinPrices)
                 })
+                .insert(|it| {
+                    it.text(fl!("tab-dca-plans"))
+                        .data(PaidEntriesPageTabs::DcaPlans)
+                })
+                .insert(|it| {
+                    it.text(fl!("tab-performance"))
+                        .data(PaidEntriesPageTabs::Performance)
+                })
+                .insert(|it| {
+                    it.text(fl!("tab-wallets"))
+                        .data(PaidEntriesPageTabs::Wallets)
+                })
                 .build(),
+            dca_frequency_options: DcaFrequency::ALL
+                .into_iter()
+                .map(dca_frequency_label)
+                .collect(),
+            wallet_chain_options: WalletChain::ALL
+                .into_iter()
+                .map(WalletChain::name)
+                .collect(),
+            editing_dust_threshold: format_usd_cents(config.holdings_filter.dust_threshold_cents),
+            editing_bitcoin_explorer_url: config.bitcoin_explorer_url.clone().unwrap_or_default(),
+            editing_evm_explorer_url: config.evm_explorer_url.clone().unwrap_or_default(),
+            editing_evm_explorer_api_key: config.evm_explorer_api_key.clone().unwrap_or_default(),
+            stable_coin_filter_options: StableCoinFilter::ALL
+                .into_iter()
+                .map(stable_coin_filter_label)
+                .collect(),
+            profit_filter_options: ProfitFilter::ALL
+                .into_iter()
+                .map(profit_filter_label)
+                .collect(),
+            fee_allocation_options: FeeAllocation::ALL
+                .into_iter()
+                .map(fee_allocation_label)
+                .collect(),
+            editing_holdings_search: config.holdings_filter.search.clone(),
+            editing_depeg_band: format_depeg_band(
+                config
+                    .stablecoin_depeg_band_bps
+                    .unwrap_or(stablecoins::DEFAULT_DEPEG_BAND_BPS),
+            ),
+            config,
             ..Default::default()
+        };
+        page.set_platform_filters(BTreeSet::new());
+        page.refresh_dca_schedules();
+
+        page
+    }
+
+    /// Lists `platforms` in the platform dropdown, along with the saved one so that it
+    /// shows before the quotes are in.
+    fn set_platform_filters(&mut self, mut platforms: BTreeSet<PlatformFilter>) {
+        platforms.extend(self.config.holdings_filter.platform.clone());
+
+        self.platform_filters = std::iter::once(None)
+            .chain(platforms.into_iter().map(Some))
+            .collect();
+        self.platform_filter_options = self
+            .platform_filters
+            .iter()
+            .map(|platform| {
+                platform
+                    .as_ref()
+                    .map_or_else(|| fl!("filter-all-platforms"), platform_label)
+            })
+            .collect();
+    }
+
+    fn view_dca_plans(&self) -> Element<PaidEntriesPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+        let today = chrono::Local::now().date_naive();
+
+        let form = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input(fl!("dca-symbol"), &self.dca_plan_form.symbol)
+                    .label(fl!("dca-symbol"))
+                    .on_input(PaidEntriesPageMessage::DcaSymbolInput),
+            )
+            .push(
+                widget::text_input(fl!("dca-amount"), &self.dca_plan_form.amount)
+                    .label(fl!("dca-amount"))
+                    .on_input(PaidEntriesPageMessage::DcaAmountInput),
+            )
+            .push(
+                widget::text_input("USD", &self.dca_plan_form.currency)
+                    .label(fl!("dca-currency"))
+                    .on_input(PaidEntriesPageMessage::DcaCurrencyInput),
+            )
+            .push(widget::dropdown(
+                &self.dca_frequency_options,
+                DcaFrequency::ALL
+                    .iter()
+                    .position(|it| *it == self.dca_plan_form.frequency),
+                PaidEntriesPageMessage::DcaFrequencySelected,
+            ))
+            .push(
+                widget::text_input("YYYY-MM-DD", &self.dca_plan_form.start_date)
+                    .label(fl!("dca-start-date"))
+                    .on_input(PaidEntriesPageMessage::DcaStartDateInput),
+            )
+            .push(
+                widget::button::suggested(fl!("dca-add-plan"))
+                    .on_press(PaidEntriesPageMessage::DcaPlanAdd),
+            );
+
+        let plans = self
+            .config
+            .dca_plans
+            .iter()
+            .zip(&self.dca_schedules)
+            .enumerate()
+            .fold(widget::list_column(), |plans, (index, (plan, schedule))| {
+                plans.add(self.view_dca_plan(index, plan, schedule, today))
+            });
+
+        widget::column()
+            .spacing(space_s)
+            .push(form)
+            .push(if self.config.dca_plans.is_empty() {
+                Element::from(widget::text(fl!("dca-no-plans")))
+            } else {
+                widget::scrollable(plans).into()
+            })
+            .into()
+    }
+
+    fn view_dca_plan(
+        &self,
+        index: usize,
+        plan: &DcaPlan,
+        schedule: &[ScheduledBuy],
+        today: NaiveDate,
+    ) -> Element<PaidEntriesPageMessage> {
+        let space_xxs = theme::active().cosmic().space_xxs();
+
+        let entries = self
+            .raw_json_data
+            .as_ref()
+            .and_then(|raw_json_data| raw_json_data.get(&plan.symbol))
+            .map_or(&[][..], Vec::as_slice);
+        let count_of = |status: DcaBuyStatus| {
+            schedule
+                .iter()
+                .filter(|scheduled_buy| scheduled_buy.status == status)
+                .count()
+        };
+        let overdue_dates = schedule
+            .iter()
+            .filter(|scheduled_buy| scheduled_buy.status == DcaBuyStatus::Overdue)
+            .map(|scheduled_buy| scheduled_buy.date.to_string())
+            .collect::<Vec<String>>();
+
+        let mut column = widget::column()
+            .spacing(space_xxs)
+            .push(
+                widget::row()
+                    .align_y(Vertical::Center)
+                    .push(
+                        widget::text::heading(fl!(
+                            "dca-plan-title",
+                            symbol = plan.symbol.as_str(),
+                            amount = format!("{:.2}", plan.amount()),
+                            currency = plan.currency.as_str(),
+                            frequency = dca_frequency_label(plan.frequency),
+                            start = plan.start_date.to_string()
+                        ))
+                        .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                            .on_press(PaidEntriesPageMessage::DcaPlanRemove(index)),
+                    ),
+            )
+            .push(widget::text(fl!(
+                "dca-plan-status",
+                done = count_of(DcaBuyStatus::Done),
+                due = count_of(DcaBuyStatus::Due),
+                overdue = count_of(DcaBuyStatus::Overdue)
+            )))
+            .push_maybe(
+                schedule
+                    .iter()
+                    .find(|scheduled_buy| scheduled_buy.status == DcaBuyStatus::Upcoming)
+                    .map(|scheduled_buy| {
+                        widget::text(fl!(
+                            "dca-plan-next-buy",
+                            date = scheduled_buy.date.to_string()
+                        ))
+                    }),
+            )
+            .push_maybe((!overdue_dates.is_empty()).then(|| {
+                widget::text(fl!(
+                    "dca-plan-overdue-dates",
+                    dates = overdue_dates.join(", ")
+                ))
+            }));
+
+        let current_price = self
+            .crypto_names_to_prices
+            .as_ref()
+            .and_then(|prices| portfolio::latest_usd_price(prices, &plan.symbol));
+
+        column = match current_price {
+            Some(current_price) if plan.is_in_usd() => {
+                let holding = portfolio::CoinHolding::from_entries(
+                    &plan.symbol,
+                    entries,
+                    self.config.fee_allocation,
+                );
+
+                plan.project(&holding, current_price, today)
+                    .into_iter()
+                    .fold(column, |column, projection| {
+                        column.push(widget::text::monotext(fl!(
+                            "dca-projection",
+                            scenario = price_scenario_label(projection.scenario),
+                            months = dca::PROJECTION_MONTHS,
+                            buys = projection.buys,
+                            cost = format!("{:.2}", projection.total_cost_usd),
+                            amount = format!("{:.8}", projection.holding_amount),
+                            value = format!("{:.2}", projection.value_usd)
+                        )))
+                    })
+            }
+            Some(_) => column.push(widget::text(fl!("dca-projection-usd-only"))),
+            None => column.push(widget::text(fl!("dca-projection-no-price"))),
+        };
+
+        column.into()
+    }
+
+    fn view_performance(&self) -> Element<PaidEntriesPageMessage> {
+        let space_xxs = theme::active().cosmic().space_xxs();
+
+        let load_button = widget::button::standard(if self.is_loading_historical_prices {
+            fl!("benchmark-loading")
+        } else {
+            fl!("benchmark-load")
+        })
+        .apply(|widget| {
+            let has_price_source =
+                self.config.demo_mode || self.config.coin_market_cap_api_key.is_some();
+
+            match (
+                has_price_source,
+                &self.raw_json_data,
+                self.is_loading_historical_prices,
+            ) {
+                (true, Some(_), false) => {
+                    widget.on_press(PaidEntriesPageMessage::HistoricalPricesLoad)
+                }
+                _ => widget,
+            }
+        });
+
+        self.benchmarks
+            .iter()
+            .fold(
+                widget::column().spacing(space_xxs).push(load_button),
+                |column, (kind, benchmark)| {
+                    column.push(widget::text::monotext(match benchmark {
+                        Some(benchmark) => fl!(
+                            "benchmark-row",
+                            name = benchmark_label(*kind),
+                            invested = format!("{:.2}", benchmark.invested_usd),
+                            value = format!("{:.2}", benchmark.value_usd),
+                            twr = format!("{:+.2}%", benchmark.time_weighted_return * 100.0),
+                            mwr = benchmark.money_weighted_return.map_or_else(
+                                || "N/A".to_owned(),
+                                |it| format!("{:+.2}%", it * 100.0)
+                            )
+                        ),
+                        None => fl!("benchmark-row-na", name = benchmark_label(*kind)),
+                    }))
+                },
+            )
+            .into()
+    }
+
+    /// Matches the entries against every plan, so that view doesn't redo it on every frame.
+    fn refresh_dca_schedules(&mut self) {
+        let today = chrono::Local::now().date_naive();
+
+        self.dca_schedules = self
+            .config
+            .dca_plans
+            .iter()
+            .map(|plan| {
+                let entries = self
+                    .raw_json_data
+                    .as_ref()
+                    .and_then(|raw_json_data| raw_json_data.get(&plan.symbol))
+                    .map_or(&[][..], Vec::as_slice);
+
+                plan.schedule(entries, today)
+            })
+            .collect();
+    }
+
+    /// Replays the entries into every benchmark once the history is in, so that view
+    /// doesn't redo it on every frame.
+    fn refresh_benchmarks(&mut self) {
+        self.benchmarks = match (&self.raw_json_data, &self.historical_prices) {
+            (Some(raw_json_data), Some(historical_prices)) => {
+                let latest_prices: HashMap<String, f64> = self
+                    .crypto_names_to_prices
+                    .as_ref()
+                    .map(|prices| {
+                        prices
+                            .keys()
+                            .filter_map(|symbol| {
+                                Some((symbol.clone(), portfolio::latest_usd_price(prices, symbol)?))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                benchmark::benchmarks(
+                    &benchmark::cash_flows(raw_json_data),
+                    historical_prices,
+                    &latest_prices,
+                    chrono::Local::now().date_naive(),
+                )
+            }
+            _ => vec![],
+        };
+    }
+
+    fn view_wallets(&self) -> Element<PaidEntriesPageMessage> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let is_explorer_settings_edited = [
+            (
+                &self.editing_bitcoin_explorer_url,
+                &self.config.bitcoin_explorer_url,
+            ),
+            (
+                &self.editing_evm_explorer_url,
+                &self.config.evm_explorer_url,
+            ),
+            (
+                &self.editing_evm_explorer_api_key,
+                &self.config.evm_explorer_api_key,
+            ),
+        ]
+        .into_iter()
+        .any(|(editing, saved)| editing.as_str() != saved.as_deref().unwrap_or_default());
+
+        let explorer_settings = widget::column()
+            .spacing(space_xxs)
+            .push(
+                widget::text_input(
+                    BlockExplorer::BITCOIN_URL,
+                    &self.editing_bitcoin_explorer_url,
+                )
+                .label(fl!("bitcoin-explorer-url"))
+                .on_input(PaidEntriesPageMessage::BitcoinExplorerUrlInput),
+            )
+            .push(
+                widget::text_input(BlockExplorer::EVM_URL, &self.editing_evm_explorer_url)
+                    .label(fl!("evm-explorer-url"))
+                    .on_input(PaidEntriesPageMessage::EvmExplorerUrlInput),
+            )
+            .push(
+                widget::text_input(
+                    fl!("evm-explorer-api-key"),
+                    &self.editing_evm_explorer_api_key,
+                )
+                .label(fl!("evm-explorer-api-key"))
+                .password()
+                .on_input(PaidEntriesPageMessage::EvmExplorerApiKeyInput),
+            )
+            .push(
+                widget::button::suggested(fl!("explorer-settings-save")).apply(|widget| {
+                    if is_explorer_settings_edited {
+                        widget.on_press(PaidEntriesPageMessage::ExplorerSettingsSubmit)
+                    } else {
+                        widget
+                    }
+                }),
+            );
+
+        let form = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(widget::dropdown(
+                &self.wallet_chain_options,
+                WalletChain::ALL
+                    .iter()
+                    .position(|it| *it == self.wallet_form.chain),
+                PaidEntriesPageMessage::WalletChainSelected,
+            ))
+            .push(
+                widget::text_input(fl!("wallet-address"), &self.wallet_form.address)
+                    .label(fl!("wallet-address"))
+                    .on_input(PaidEntriesPageMessage::WalletAddressInput),
+            )
+            .push(
+                widget::text_input(fl!("wallet-label"), &self.wallet_form.label)
+                    .label(fl!("wallet-label"))
+                    .on_input(PaidEntriesPageMessage::WalletLabelInput),
+            )
+            .push(
+                widget::button::suggested(fl!("wallet-add")).apply(|widget| {
+                    if self.wallet_form.is_valid() {
+                        widget.on_press(PaidEntriesPageMessage::WalletAdd)
+                    } else {
+                        widget
+                    }
+                }),
+            );
+
+        let wallet_list = self.config.wallets.iter().enumerate().fold(
+            widget::list_column(),
+            |wallet_list, (index, wallet)| {
+                let balance = self
+                    .wallet_balances
+                    .as_ref()
+                    .and_then(|balances| balances.iter().find(|(it, _)| it == wallet))
+                    .map_or_else(
+                        || fl!("wallet-balance-na"),
+                        |(_, balance)| match balance {
+                            Ok(balance) => format!("{balance} {}", wallet.chain.symbol()),
+                            Err(e) => fl!("wallet-balance-error", error = e.as_str()),
+                        },
+                    );
+
+                wallet_list.add(
+                    widget::row()
+                        .align_y(Vertical::Center)
+                        .spacing(space_xxs)
+                        .push(
+                            widget::column()
+                                .push(widget::text::heading(if wallet.label.is_empty() {
+                                    wallet.chain.name().to_owned()
+                                } else {
+                                    format!("{} ({})", wallet.label, wallet.chain.name())
+                                }))
+                                .push(widget::text::monotext(&wallet.address))
+                                .width(Length::Fill),
+                        )
+                        .push(widget::text(balance))
+                        .push(
+                            widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                                .on_press(PaidEntriesPageMessage::WalletRemove(index)),
+                        ),
+                )
+            },
+        );
+
+        let reconciliations = match (&self.raw_json_data, &self.wallet_balances) {
+            (Some(raw_json_data), Some(wallet_balances)) => wallets::reconcile(
+                &portfolio::holdings(raw_json_data, self.config.fee_allocation),
+                wallet_balances,
+            ),
+            _ => vec![],
+        };
+
+        reconciliations
+            .into_iter()
+            .fold(
+                widget::column()
+                    .spacing(space_s)
+                    .push(explorer_settings)
+                    .push(form)
+                    .push(
+                        widget::button::standard(if self.is_fetching_wallet_balances {
+                            fl!("wallet-balances-fetching")
+                        } else {
+                            fl!("wallet-balances-refresh")
+                        })
+                        .apply(|widget| {
+                            if self.config.wallets.is_empty() || self.is_fetching_wallet_balances {
+                                widget
+                            } else {
+                                widget.on_press(PaidEntriesPageMessage::WalletBalancesRefresh)
+                            }
+                        }),
+                    )
+                    .push(wallet_list),
+                |column, reconciliation| {
+                    column.push(widget::text(fl!(
+                        "wallet-reconciliation",
+                        symbol = reconciliation.symbol.as_str(),
+                        recorded = reconciliation.recorded,
+                        on_chain = reconciliation.on_chain,
+                        status = if reconciliation.is_discrepancy() {
+                            fl!("wallet-reconciliation-mismatch")
+                        } else {
+                            fl!("wallet-reconciliation-ok")
+                        }
+                    )))
+                },
+            )
+            .into()
+    }
+
+    fn view_holdings(&self) -> Option<Element<PaidEntriesPageMessage>> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let holdings =
+            portfolio::holdings(self.raw_json_data.as_ref()?, self.config.fee_allocation);
+        let depeg_band_bps = self
+            .config
+            .stablecoin_depeg_band_bps
+            .unwrap_or(stablecoins::DEFAULT_DEPEG_BAND_BPS);
+        let price_of = |holding: &CoinHolding| {
+            self.crypto_names_to_prices
+                .as_ref()
+                .and_then(|prices| portfolio::latest_usd_price(prices, &holding.symbol))
+        };
+
+        let settings = widget::row()
+            .align_y(Vertical::Center)
+            .spacing(space_xxs)
+            .push(
+                widget::toggler(self.config.exclude_stable_coins_from_pnl)
+                    .on_toggle(PaidEntriesPageMessage::ExcludeStableCoinsToggled),
+            )
+            .push(widget::text(fl!("exclude-stable-coins-from-pnl")).width(Length::Fill))
+            .push(
+                widget::text_input("1.00", &self.editing_depeg_band)
+                    .label(fl!("depeg-band-percent"))
+                    .on_input(PaidEntriesPageMessage::DepegBandInput)
+                    .width(Length::Fixed(120.0)),
+            )
+            .push(widget::dropdown(
+                &self.fee_allocation_options,
+                FeeAllocation::ALL
+                    .iter()
+                    .position(|it| *it == self.config.fee_allocation),
+                PaidEntriesPageMessage::FeeAllocationSelected,
+            ));
+
+        let mut column = widget::column().spacing(space_s).push(settings);
+
+        if let Some(prices) = &self.crypto_names_to_prices {
+            let summary =
+                PortfolioSummary::new(&holdings, prices, self.config.exclude_stable_coins_from_pnl);
+
+            column = column.push(widget::text::heading(fl!(
+                "portfolio-summary",
+                cost = format!("{:.2}", summary.cost_usd),
+                value = format!("{:.2}", summary.value_usd),
+                pnl = format!("{:+.2}", summary.pnl_usd()),
+                cash = format!("{:.2}", summary.cash_like_usd)
+            )));
+
+            if summary.fees_usd > 0.0 {
+                column = column.push(widget::text(fl!(
+                    "fees-summary",
+                    fees = format!("{:.2}", summary.fees_usd),
+                    allocation = fee_allocation_label(self.config.fee_allocation)
+                )));
+            }
+            if summary.unvalued_fees > 0 {
+                column = column.push(widget::text(fl!(
+                    "fees-unvalued",
+                    count = summary.unvalued_fees
+                )));
+            }
+        }
+
+        column = holdings
+            .iter()
+            .filter(|holding| holding.is_stable_coin)
+            .filter_map(|holding| {
+                stablecoins::depeg(&holding.symbol, price_of(holding)?, depeg_band_bps)
+            })
+            .fold(column, |column, depeg| {
+                column.push(widget::text(fl!(
+                    "depeg-warning",
+                    symbol = depeg.symbol.as_str(),
+                    price = format!("{:.4}", depeg.price),
+                    deviation = format!("{:+.2}%", depeg.deviation_bps / 100.0)
+                )))
+            });
+
+        let holdings_filter = &HoldingsFilter {
+            search: self.editing_holdings_search.clone(),
+            ..self.config.holdings_filter.clone()
+        };
+        let records_of = |holding: &CoinHolding| {
+            self.crypto_names_to_prices
+                .as_ref()
+                .and_then(|prices| prices.get(&holding.symbol))
+        };
+
+        let filter_bar = widget::row()
+            .align_y(Vertical::Bottom)
+            .spacing(space_xxs)
+            .push(
+                widget::text_input(fl!("holdings-search"), &self.editing_holdings_search)
+                    .label(fl!("holdings-search"))
+                    .on_input(PaidEntriesPageMessage::HoldingsSearchInput),
+            )
+            .push(widget::dropdown(
+                &self.platform_filter_options,
+                self.platform_filters
+                    .iter()
+                    .position(|it| *it == holdings_filter.platform),
+                PaidEntriesPageMessage::HoldingsPlatformSelected,
+            ))
+            .push(widget::dropdown(
+                &self.stable_coin_filter_options,
+                StableCoinFilter::ALL
+                    .iter()
+                    .position(|it| *it == holdings_filter.stable_coins),
+                PaidEntriesPageMessage::HoldingsStableCoinFilterSelected,
+            ))
+            .push(widget::dropdown(
+                &self.profit_filter_options,
+                ProfitFilter::ALL
+                    .iter()
+                    .position(|it| *it == holdings_filter.profit),
+                PaidEntriesPageMessage::HoldingsProfitFilterSelected,
+            ))
+            .push(
+                widget::text_input("0.00", &self.editing_dust_threshold)
+                    .label(fl!("holdings-dust-threshold"))
+                    .on_input(PaidEntriesPageMessage::HoldingsDustThresholdInput)
+                    .width(Length::Fixed(120.0)),
+            )
+            .push(
+                widget::toggler(holdings_filter.group_by_platform)
+                    .on_toggle(PaidEntriesPageMessage::HoldingsGroupByPlatformToggled),
+            )
+            .push(widget::text(fl!("holdings-group-by-platform")));
+
+        column = column.push(filter_bar);
+
+        let (cash_like, coins): (Vec<&CoinHolding>, Vec<&CoinHolding>) = holdings
+            .iter()
+            .filter(|holding| {
+                holdings_filter.matches(holding, price_of(holding), records_of(holding))
+            })
+            .partition(|holding| holding.is_stable_coin);
+
+        let holding_list = |holdings: Vec<&CoinHolding>, show_pnl: bool| {
+            holdings
+                .into_iter()
+                .fold(widget::list_column(), |list, holding| {
+                    list.add(widget::text::monotext(match price_of(holding) {
+                        Some(price) if show_pnl => fl!(
+                            "holding-row-pnl",
+                            symbol = holding.symbol.as_str(),
+                            amount = holding.amount,
+                            cost = format!("{:.2}", holding.cost_usd),
+                            value = format!("{:.2}", holding.value_usd(price)),
+                            pnl = format!("{:+.2}", holding.pnl_usd(price))
+                        ),
+                        Some(price) => fl!(
+                            "holding-row",
+                            symbol = holding.symbol.as_str(),
+                            amount = holding.amount,
+                            value = format!("{:.2}", holding.value_usd(price))
+                        ),
+                        None => fl!(
+                            "holding-row-no-price",
+                            symbol = holding.symbol.as_str(),
+                            amount = holding.amount
+                        ),
+                    }))
+                })
+        };
+
+        if holdings_filter.group_by_platform {
+            let mut coins_by_platform: BTreeMap<PlatformFilter, Vec<&CoinHolding>> =
+                BTreeMap::new();
+            for coin in coins {
+                coins_by_platform
+                    .entry(filters::platform_of(records_of(coin)))
+                    .or_default()
+                    .push(coin);
+            }
+
+            for (platform, coins) in coins_by_platform {
+                column = column
+                    .push(widget::text::heading(platform_label(&platform)))
+                    .push(holding_list(coins, true));
+            }
+        } else if !coins.is_empty() {
+            column = column
+                .push(widget::text::heading(fl!("holdings-coins")))
+                .push(holding_list(coins, true));
+        }
+
+        if !cash_like.is_empty() {
+            column = column
+                .push(widget::text::heading(fl!("holdings-cash-like")))
+                .push(holding_list(
+                    cash_like,
+                    !self.config.exclude_stable_coins_from_pnl,
+                ));
+        }
+
+        Some(column.into())
+    }
+
+    fn set_holdings_filter(&mut self, holdings_filter: HoldingsFilter) {
+        if let Some(config_handler) = self.config_handler.as_ref() {
+            if let Err(e) = self
+                .config
+                .set_holdings_filter(config_handler, holdings_filter)
+            {
+                tracing::error!("Error set_holdings_filter: {e}");
+            }
         }
     }
 
+    /// Where file dialogs open: next to the paid entries JSON, if any.
+    fn json_directory(&self) -> PathBuf {
+        self.config
+            .paid_entries_json_path
+            .clone()
+            .map_or(PathBuf::new(), |path| {
+                path.parent().unwrap_or(&path).to_owned()
+            })
+    }
+
     fn load_paid_entries_json(json_path: PathBuf) -> Task<PaidEntriesPageMessage> {
         Task::future(async move {
             match tokio::fs::read_to_string(json_path).await {
@@ -149,23 +991,21 @@ impl PaidEntriesPage {
         })
     }
 
+    /// The bundled sample portfolio, which then gets the fixture prices.
+    fn load_demo_paid_entries() -> Task<PaidEntriesPageMessage> {
+        Task::done(match demo::paid_entries() {
+            Ok(raw_json_data) => PaidEntriesPageMessage::RawJsonLoaded(raw_json_data),
+            Err(e) => PaidEntriesPageMessage::RawJsonLoadingFailed(e),
+        })
+    }
+
     fn load_crypto_prices(api_key: String, symbols: Vec<String>) -> Task<PaidEntriesPageMessage> {
         Task::future(async move {
-            let url = format!(
-                "https://pro-api.coinmarketcap.com/v2/cryptocurrency/quotes/latest?symbol={}",
-                symbols.join(",")
-            );
-
-            match reqwest::Client::new()
-                .get(url)
-                .header("X-CMC_PRO_API_KEY", api_key)
-                .send()
+            match CoinMarketCapProvider::new(api_key)
+                .latest_quotes(&symbols)
                 .await
             {
-                Ok(response) => match response.json::<CoinApiResponse>().await {
-                    Ok(response) => PaidEntriesPageMessage::CryptoPricesFetched(response.data),
-                    Err(e) => PaidEntriesPageMessage::CryptoPricesFetchingFailed(e.to_string()),
-                },
+                Ok(data) => PaidEntriesPageMessage::CryptoPricesFetched(data),
                 Err(e) => {
                     tracing::error!("{e}");
                     PaidEntriesPageMessage::CryptoPricesFetchingFailed(e.to_string())
@@ -173,6 +1013,118 @@ impl PaidEntriesPage {
             }
         })
     }
+
+    fn load_wallet_balances(
+        explorer: BlockExplorer,
+        wallets: Vec<WalletAddress>,
+    ) -> Task<PaidEntriesPageMessage> {
+        Task::future(async move {
+            let balances =
+                futures_util::future::join_all(wallets.iter().map(|it| explorer.balance(it))).await;
+
+            PaidEntriesPageMessage::WalletBalancesFetched(
+                wallets
+                    .into_iter()
+                    .zip(balances)
+                    .map(|(wallet, balance)| {
+                        (
+                            wallet,
+                            balance.map_err(|e| {
+                                tracing::error!("load_wallet_balances failed: {e}");
+                                e.to_string()
+                            }),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+    }
+
+    fn load_historical_prices(
+        api_key: String,
+        symbols: Vec<String>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Task<PaidEntriesPageMessage> {
+        Task::future(async move {
+            match CoinMarketCapProvider::new(api_key)
+                .daily_history(&symbols, from, to)
+                .await
+            {
+                Ok(historical_prices) => {
+                    PaidEntriesPageMessage::HistoricalPricesFetched(historical_prices)
+                }
+                Err(e) => {
+                    tracing::error!("load_historical_prices failed: {e}");
+                    PaidEntriesPageMessage::HistoricalPricesFetchingFailed(e.to_string())
+                }
+            }
+        })
+    }
+}
+
+fn dca_frequency_label(frequency: DcaFrequency) -> String {
+    match frequency {
+        DcaFrequency::Daily => fl!("dca-frequency-daily"),
+        DcaFrequency::Weekly => fl!("dca-frequency-weekly"),
+        DcaFrequency::Biweekly => fl!("dca-frequency-biweekly"),
+        DcaFrequency::Monthly => fl!("dca-frequency-monthly"),
+    }
+}
+
+fn fee_allocation_label(fee_allocation: FeeAllocation) -> String {
+    match fee_allocation {
+        FeeAllocation::Capitalize => fl!("fee-allocation-capitalize"),
+        FeeAllocation::Expense => fl!("fee-allocation-expense"),
+    }
+}
+
+fn stable_coin_filter_label(stable_coin_filter: StableCoinFilter) -> String {
+    match stable_coin_filter {
+        StableCoinFilter::All => fl!("filter-stable-coins-all"),
+        StableCoinFilter::Only => fl!("filter-stable-coins-only"),
+        StableCoinFilter::Exclude => fl!("filter-stable-coins-exclude"),
+    }
+}
+
+fn profit_filter_label(profit_filter: ProfitFilter) -> String {
+    match profit_filter {
+        ProfitFilter::All => fl!("filter-profit-all"),
+        ProfitFilter::InProfit => fl!("filter-profit-in-profit"),
+        ProfitFilter::AtLoss => fl!("filter-profit-at-loss"),
+    }
+}
+
+fn platform_label(platform: &PlatformFilter) -> String {
+    match platform {
+        PlatformFilter::Native => fl!("platform-native"),
+        PlatformFilter::Named(name) => name.clone(),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_usd_cents(cents: u64) -> String {
+    format!("{:.2}", cents as f64 / 100.0)
+}
+
+fn format_depeg_band(band_bps: u32) -> String {
+    format!("{:.2}", f64::from(band_bps) / 100.0)
+}
+
+fn benchmark_label(kind: BenchmarkKind) -> String {
+    match kind {
+        BenchmarkKind::Portfolio => fl!("benchmark-portfolio"),
+        BenchmarkKind::Btc => fl!("benchmark-btc"),
+        BenchmarkKind::Eth => fl!("benchmark-eth"),
+        BenchmarkKind::Fiat => fl!("benchmark-fiat"),
+    }
+}
+
+fn price_scenario_label(scenario: PriceScenario) -> String {
+    fl!(
+        "dca-scenario",
+        change = format!("{:+}%", scenario.yearly_change_percent())
+    )
 }
 
 impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
@@ -183,10 +1135,26 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
         widget::column::with_children(vec![
             widget::tab_bar::horizontal(&self.tab_model)
                 .on_activate(PaidEntriesPageMessage::SwitchTab)
-                .width(cosmic::iced::Length::Fill)
+                .width(Length::Fill)
                 .into(),
             match self.tab_model.active_data::<PaidEntriesPageTabs>() {
                 Some(PaidEntriesPageTabs::CoinBalance) => widget::column()
+                    .push(
+                        widget::row()
+                            .align_y(Vertical::Center)
+                            .spacing(cosmic_theme.space_xxs())
+                            .push(
+                                widget::toggler(self.config.demo_mode)
+                                    .on_toggle(PaidEntriesPageMessage::DemoModeToggled),
+                            )
+                            .push(widget::text(fl!("demo-mode")))
+                            .push_maybe(
+                                self.config
+                                    .demo_mode
+                                    .then(|| widget::text::caption(fl!("demo-mode-description"))),
+                            ),
+                    )
+                    .push(widget::Space::with_height(cosmic_theme.space_xxs()))
                     .push(widget::text(fl!("cmc-api-key")))
                     .push(widget::Space::with_height(cosmic_theme.space_xxs()))
                     .push(
@@ -251,20 +1219,54 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
                                 .into(),
                         ),
                     )
-                    .push_maybe(self.raw_json_data.as_ref().map(|raw_json_data| {
-                        widget::text(format!(
-                            "You got {}",
-                            raw_json_data
-                                .keys()
-                                .map(|it| &**it)
-                                .collect::<Vec<&str>>()
-                                .join(", ")
-                        ))
-                    }))
+                    .push(
+                        widget::row()
+                            .spacing(cosmic_theme.space_xxs())
+                            .push(widget::button::standard(fl!("export-beancount")).apply(
+                                |widget| {
+                                    if self.raw_json_data.is_some() {
+                                        widget.on_press(PaidEntriesPageMessage::LedgerExport(
+                                            LedgerFormat::Beancount,
+                                        ))
+                                    } else {
+                                        widget
+                                    }
+                                },
+                            ))
+                            .push(
+                                widget::button::standard(fl!("export-hledger")).apply(|widget| {
+                                    if self.raw_json_data.is_some() {
+                                        widget.on_press(PaidEntriesPageMessage::LedgerExport(
+                                            LedgerFormat::Hledger,
+                                        ))
+                                    } else {
+                                        widget
+                                    }
+                                }),
+                            )
+                            .push(
+                                widget::button::standard(fl!("import-journal"))
+                                    .on_press(PaidEntriesPageMessage::LedgerImport),
+                            )
+                            .push(
+                                widget::button::standard(fl!("export-report")).apply(|widget| {
+                                    if self.raw_json_data.is_some() {
+                                        widget.on_press(PaidEntriesPageMessage::ReportExport)
+                                    } else {
+                                        widget
+                                    }
+                                }),
+                            ),
+                    )
+                    .push_maybe(self.view_holdings())
+                    .apply(widget::scrollable)
                     .into(),
                 Some(PaidEntriesPageTabs::CoinPrices) => {
                     widget::column().push(widget::text("N/A")).into()
                 }
+                Some(PaidEntriesPageTabs::DcaPlans) => self.view_dca_plans(),
+                Some(PaidEntriesPageTabs::Performance) => self.view_performance(),
+                Some(PaidEntriesPageTabs::Wallets) => self.view_wallets(),
                 None => {
                     tracing::warn!("No tab activate?");
 
@@ -281,14 +1283,7 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
                 if let Some(json_path) = rfd::FileDialog::new()
                     .set_title(fl!("pick-json-dialog-title"))
                     .add_filter("json", &["json"])
-                    .set_directory(
-                        self.config
-                            .paid_entries_json_path
-                            .clone()
-                            .map_or(PathBuf::new(), |path| {
-                                path.parent().unwrap_or(&path).to_owned()
-                            }),
-                    )
+                    .set_directory(self.json_directory())
                     .pick_file()
                 {
                     if let Some(config_handler) = self.config_handler.as_ref() {
@@ -298,6 +1293,10 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
                         {
                             tracing::error!("Error set_paid_entries_json_path: {e}");
                         }
+                        // A file of one's own replaces the sample portfolio
+                        if let Err(e) = self.config.set_demo_mode(config_handler, false) {
+                            tracing::error!("Error set_demo_mode: {e}");
+                        }
                     }
 
                     return Self::load_paid_entries_json(json_path).map(|it| match it {
@@ -319,6 +1318,15 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
             PaidEntriesPageMessage::RawJsonLoaded(raw_json_data) => {
                 self.paid_entries_json_load_state = PaidEntriesJsonLoadState::Loaded;
                 self.raw_json_data = Some(raw_json_data.clone());
+                self.refresh_benchmarks();
+                self.refresh_dca_schedules();
+
+                if self.config.demo_mode {
+                    return Task::done(match demo::latest_quotes() {
+                        Ok(data) => PaidEntriesPageMessage::CryptoPricesFetched(data),
+                        Err(e) => PaidEntriesPageMessage::CryptoPricesFetchingFailed(e),
+                    });
+                }
 
                 if let Some(api_key) = &self.config.coin_market_cap_api_key {
                     let symbols = raw_json_data
@@ -367,7 +1375,14 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
                 self.is_edit_api_key_unlocked = false;
             }
             PaidEntriesPageMessage::CryptoPricesFetched(crypto_names_to_prices) => {
+                let platforms: BTreeSet<PlatformFilter> = crypto_names_to_prices
+                    .values()
+                    .map(|records| filters::platform_of(Some(records)))
+                    .collect();
+
+                self.set_platform_filters(platforms);
                 self.crypto_names_to_prices = Some(crypto_names_to_prices);
+                self.refresh_benchmarks();
             }
             PaidEntriesPageMessage::CryptoPricesFetchingFailed(error_message) => {
                 self.dialog = Some(PaidEntriesDialogContent::Error(DialogContent {
@@ -378,6 +1393,537 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
             PaidEntriesPageMessage::SwitchTab(id) => {
                 self.tab_model.activate(id);
             }
+            PaidEntriesPageMessage::DcaSymbolInput(symbol) => {
+                self.dca_plan_form.symbol = symbol;
+            }
+            PaidEntriesPageMessage::DcaAmountInput(amount) => {
+                self.dca_plan_form.amount = amount;
+            }
+            PaidEntriesPageMessage::DcaCurrencyInput(currency) => {
+                self.dca_plan_form.currency = currency;
+            }
+            PaidEntriesPageMessage::DcaFrequencySelected(index) => {
+                if let Some(frequency) = DcaFrequency::ALL.get(index) {
+                    self.dca_plan_form.frequency = *frequency;
+                }
+            }
+            PaidEntriesPageMessage::DcaStartDateInput(start_date) => {
+                self.dca_plan_form.start_date = start_date;
+            }
+            PaidEntriesPageMessage::DcaPlanAdd => {
+                match self
+                    .dca_plan_form
+                    .to_plan(chrono::Local::now().date_naive())
+                {
+                    Ok(plan) => {
+                        let mut dca_plans = self.config.dca_plans.clone();
+                        dca_plans.push(plan);
+
+                        if let Some(config_handler) = self.config_handler.as_ref() {
+                            if let Err(e) = self.config.set_dca_plans(config_handler, dca_plans) {
+                                tracing::error!("Error set_dca_plans: {e}");
+                            } else {
+                                self.dca_plan_form = DcaPlanForm::default();
+                            }
+                        }
+                        self.refresh_dca_schedules();
+                    }
+                    Err(e) => {
+                        self.dialog = Some(PaidEntriesDialogContent::Error(DialogContent {
+                            title: fl!("error-invalid-dca-plan"),
+                            body: match e {
+                                DcaPlanFormError::MissingSymbol => fl!("dca-error-missing-symbol"),
+                                DcaPlanFormError::InvalidAmount => fl!("dca-error-invalid-amount"),
+                                DcaPlanFormError::InvalidStartDate => {
+                                    fl!("dca-error-invalid-start-date")
+                                }
+                            },
+                        }));
+                    }
+                }
+            }
+            PaidEntriesPageMessage::DcaPlanRemove(index) => {
+                if index < self.config.dca_plans.len() {
+                    let mut dca_plans = self.config.dca_plans.clone();
+                    dca_plans.remove(index);
+
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self.config.set_dca_plans(config_handler, dca_plans) {
+                            tracing::error!("Error set_dca_plans: {e}");
+                        }
+                    }
+                    self.refresh_dca_schedules();
+                }
+            }
+            PaidEntriesPageMessage::HistoricalPricesLoad if self.config.demo_mode => {
+                return Task::done(match demo::historical_prices() {
+                    Ok(historical_prices) => {
+                        PaidEntriesPageMessage::HistoricalPricesFetched(historical_prices)
+                    }
+                    Err(e) => PaidEntriesPageMessage::HistoricalPricesFetchingFailed(e),
+                });
+            }
+            PaidEntriesPageMessage::HistoricalPricesLoad => {
+                if let (Some(api_key), Some(raw_json_data)) =
+                    (&self.config.coin_market_cap_api_key, &self.raw_json_data)
+                {
+                    let today = chrono::Local::now().date_naive();
+                    let from = benchmark::cash_flows(raw_json_data)
+                        .first()
+                        .map_or(today, |cash_flow| cash_flow.date);
+                    let mut symbols: Vec<String> = raw_json_data.keys().cloned().collect();
+                    for symbol in BenchmarkKind::ALL
+                        .into_iter()
+                        .filter_map(BenchmarkKind::symbol)
+                    {
+                        if !symbols.iter().any(|it| it == symbol) {
+                            symbols.push(symbol.to_owned());
+                        }
+                    }
+
+                    self.is_loading_historical_prices = true;
+
+                    return Self::load_historical_prices(api_key.clone(), symbols, from, today);
+                }
+            }
+            PaidEntriesPageMessage::HistoricalPricesFetched(historical_prices) => {
+                self.is_loading_historical_prices = false;
+                self.historical_prices = Some(historical_prices);
+                self.refresh_benchmarks();
+            }
+            PaidEntriesPageMessage::HistoricalPricesFetchingFailed(error_message) => {
+                self.is_loading_historical_prices = false;
+                self.dialog = Some(PaidEntriesDialogContent::Error(DialogContent {
+                    title: fl!("error-fetching-historical-prices"),
+                    body: error_message,
+                }));
+            }
+            PaidEntriesPageMessage::LedgerExport(format) => {
+                if let Some(raw_json_data) = &self.raw_json_data {
+                    if let Some(journal_path) = rfd::FileDialog::new()
+                        .set_title(fl!("export-journal-dialog-title"))
+                        .add_filter(format.extension(), &[format.extension()])
+                        .set_directory(self.json_directory())
+                        .set_file_name(format!("paid_entries.{}", format.extension()))
+                        .save_file()
+                    {
+                        let journal = Journal::from_entries(
+                            raw_json_data,
+                            self.crypto_names_to_prices.as_ref(),
+                            chrono::Local::now().date_naive(),
+                        );
+                        let skipped_entries = journal.skipped_entries;
+                        let rendered = match journal.render(format) {
+                            Ok(rendered) => rendered,
+                            Err(e) => {
+                                tracing::error!("LedgerExport failed: {e}");
+                                return Task::done(PaidEntriesPageMessage::LedgerFileFailed(
+                                    e.to_string(),
+                                ));
+                            }
+                        };
+
+                        return Task::future(async move {
+                            match tokio::fs::write(&journal_path, rendered).await {
+                                Ok(()) => PaidEntriesPageMessage::LedgerExported(
+                                    journal_path,
+                                    skipped_entries,
+                                ),
+                                Err(e) => {
+                                    tracing::error!("LedgerExport failed: {e}");
+                                    PaidEntriesPageMessage::LedgerFileFailed(e.to_string())
+                                }
+                            }
+                        });
+                    }
+                }
+            }
+            PaidEntriesPageMessage::LedgerExported(journal_path, skipped_entries) => {
+                self.dialog = Some(PaidEntriesDialogContent::Success(DialogContent {
+                    title: fl!("journal-exported"),
+                    body: fl!(
+                        "journal-exported-body",
+                        path = journal_path.display().to_string(),
+                        skipped = skipped_entries
+                    ),
+                }));
+            }
+            PaidEntriesPageMessage::LedgerImport => {
+                if let Some(journal_path) = rfd::FileDialog::new()
+                    .set_title(fl!("import-journal-dialog-title"))
+                    .add_filter("beancount", &["beancount"])
+                    .add_filter("hledger", &["journal", "hledger", "ledger"])
+                    .set_directory(self.json_directory())
+                    .pick_file()
+                {
+                    let format = LedgerFormat::from_extension(
+                        journal_path
+                            .extension()
+                            .and_then(std::ffi::OsStr::to_str)
+                            .unwrap_or_default(),
+                    );
+
+                    return Task::future(async move {
+                        match tokio::fs::read_to_string(&journal_path).await {
+                            Ok(input) => {
+                                let journal = Journal::parse(&input, format);
+                                for skipped_posting in &journal.skipped_postings {
+                                    tracing::warn!("LedgerImport skipped {skipped_posting}");
+                                }
+
+                                match journal.skipped_postings.first() {
+                                    Some(e) if journal.transactions.is_empty() => {
+                                        PaidEntriesPageMessage::LedgerFileFailed(e.to_string())
+                                    }
+                                    _ => PaidEntriesPageMessage::LedgerImported(
+                                        journal.into_entries(),
+                                    ),
+                                }
+                            }
+                            Err(e) => {
+                                tracing::error!("LedgerImport failed: {e}");
+                                PaidEntriesPageMessage::LedgerFileFailed(e.to_string())
+                            }
+                        }
+                    });
+                }
+            }
+            PaidEntriesPageMessage::LedgerImported(raw_json_data) => {
+                if let Some(json_path) = rfd::FileDialog::new()
+                    .set_title(fl!("save-imported-json-dialog-title"))
+                    .add_filter("json", &["json"])
+                    .set_directory(self.json_directory())
+                    .set_file_name("paid_entries.json")
+                    .save_file()
+                {
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self
+                            .config
+                            .set_paid_entries_json_path(config_handler, Some(json_path.clone()))
+                        {
+                            tracing::error!("Error set_paid_entries_json_path: {e}");
+                        }
+                        // The imported entries replace the sample portfolio
+                        if let Err(e) = self.config.set_demo_mode(config_handler, false) {
+                            tracing::error!("Error set_demo_mode: {e}");
+                        }
+                    }
+
+                    return Task::future(async move {
+                        let written = match serde_json::to_string_pretty(&raw_json_data) {
+                            Ok(raw_json) => tokio::fs::write(&json_path, raw_json)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        };
+
+                        match written {
+                            Ok(()) => PaidEntriesPageMessage::RawJsonUpdated(raw_json_data),
+                            Err(e) => {
+                                tracing::error!("LedgerImported failed: {e}");
+                                PaidEntriesPageMessage::LedgerFileFailed(e)
+                            }
+                        }
+                    });
+                }
+            }
+            PaidEntriesPageMessage::LedgerFileFailed(error_message) => {
+                self.dialog = Some(PaidEntriesDialogContent::Error(DialogContent {
+                    title: fl!("error-journal-file"),
+                    body: error_message,
+                }));
+            }
+            PaidEntriesPageMessage::ReportExport => {
+                if let Some(raw_json_data) = &self.raw_json_data {
+                    let today = chrono::Local::now().date_naive();
+
+                    if let Some(report_path) = ReportFormat::ALL
+                        .into_iter()
+                        .fold(rfd::FileDialog::new(), |dialog, format| {
+                            dialog.add_filter(format.extension(), &[format.extension()])
+                        })
+                        .set_title(fl!("export-report-dialog-title"))
+                        .set_directory(self.json_directory())
+                        .set_file_name(format!("portfolio_report_{today}.pdf"))
+                        .save_file()
+                    {
+                        // Anything without a known extension is saved as a PDF
+                        let (report_path, format) = match report_path
+                            .extension()
+                            .and_then(std::ffi::OsStr::to_str)
+                            .and_then(ReportFormat::from_extension)
+                        {
+                            Some(format) => (report_path, format),
+                            None => (
+                                report_path.with_extension(ReportFormat::Pdf.extension()),
+                                ReportFormat::Pdf,
+                            ),
+                        };
+
+                        let report = Report::new(
+                            &portfolio::holdings(raw_json_data, self.config.fee_allocation),
+                            &self.crypto_names_to_prices.clone().unwrap_or_default(),
+                            self.config.exclude_stable_coins_from_pnl,
+                            today,
+                        );
+
+                        // Rendered up front, the PDF document can't be sent across threads
+                        let rendered = report.render(format).map_err(|e| e.to_string());
+
+                        return Task::future(async move {
+                            let written = match rendered {
+                                Ok(rendered) => tokio::fs::write(&report_path, rendered)
+                                    .await
+                                    .map_err(|e| e.to_string()),
+                                Err(e) => Err(e),
+                            };
+
+                            match written {
+                                Ok(()) => PaidEntriesPageMessage::ReportExported(report_path),
+                                Err(e) => {
+                                    tracing::error!("ReportExport failed: {e}");
+                                    PaidEntriesPageMessage::ReportExportFailed(e)
+                                }
+                            }
+                        });
+                    }
+                }
+            }
+            PaidEntriesPageMessage::ReportExported(report_path) => {
+                self.dialog = Some(PaidEntriesDialogContent::Success(DialogContent {
+                    title: fl!("report-exported"),
+                    body: fl!(
+                        "report-exported-body",
+                        path = report_path.display().to_string()
+                    ),
+                }));
+            }
+            PaidEntriesPageMessage::ReportExportFailed(error_message) => {
+                self.dialog = Some(PaidEntriesDialogContent::Error(DialogContent {
+                    title: fl!("error-exporting-report"),
+                    body: error_message,
+                }));
+            }
+            PaidEntriesPageMessage::DemoModeToggled(demo_mode) => {
+                if let Some(config_handler) = self.config_handler.as_ref() {
+                    if let Err(e) = self.config.set_demo_mode(config_handler, demo_mode) {
+                        tracing::error!("Error set_demo_mode: {e}");
+                    }
+                }
+
+                self.raw_json_data = None;
+                self.crypto_names_to_prices = None;
+                self.historical_prices = None;
+                self.benchmarks.clear();
+                self.refresh_dca_schedules();
+                self.paid_entries_json_load_state = PaidEntriesJsonLoadState::NotLoaded;
+
+                if self.config.demo_mode {
+                    return Self::load_demo_paid_entries();
+                }
+                if let Some(json_path) = self.config.paid_entries_json_path.clone() {
+                    return Self::load_paid_entries_json(json_path);
+                }
+            }
+            PaidEntriesPageMessage::WalletChainSelected(index) => {
+                if let Some(chain) = WalletChain::ALL.get(index) {
+                    self.wallet_form.chain = *chain;
+                }
+            }
+            PaidEntriesPageMessage::WalletAddressInput(address) => {
+                self.wallet_form.address = address.trim().to_owned();
+            }
+            PaidEntriesPageMessage::WalletLabelInput(label) => {
+                self.wallet_form.label = label;
+            }
+            PaidEntriesPageMessage::WalletAdd => {
+                if self.wallet_form.is_valid() && !self.config.wallets.contains(&self.wallet_form) {
+                    let mut wallets = self.config.wallets.clone();
+                    wallets.push(self.wallet_form.clone());
+
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self.config.set_wallets(config_handler, wallets) {
+                            tracing::error!("Error set_wallets: {e}");
+                        } else {
+                            self.wallet_form = WalletAddress::default();
+                        }
+                    }
+                }
+            }
+            PaidEntriesPageMessage::WalletRemove(index) => {
+                if index < self.config.wallets.len() {
+                    let mut wallets = self.config.wallets.clone();
+                    wallets.remove(index);
+
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self.config.set_wallets(config_handler, wallets) {
+                            tracing::error!("Error set_wallets: {e}");
+                        }
+                    }
+                }
+            }
+            PaidEntriesPageMessage::WalletBalancesRefresh => {
+                self.is_fetching_wallet_balances = true;
+
+                return Self::load_wallet_balances(
+                    BlockExplorer::from_config(&self.config),
+                    self.config.wallets.clone(),
+                );
+            }
+            PaidEntriesPageMessage::WalletBalancesFetched(wallet_balances) => {
+                self.is_fetching_wallet_balances = false;
+                self.wallet_balances = Some(wallet_balances);
+            }
+            PaidEntriesPageMessage::BitcoinExplorerUrlInput(url) => {
+                self.editing_bitcoin_explorer_url = url;
+            }
+            PaidEntriesPageMessage::EvmExplorerUrlInput(url) => {
+                self.editing_evm_explorer_url = url;
+            }
+            PaidEntriesPageMessage::EvmExplorerApiKeyInput(api_key) => {
+                self.editing_evm_explorer_api_key = api_key;
+            }
+            PaidEntriesPageMessage::ExplorerSettingsSubmit => {
+                let non_empty =
+                    |value: &String| Some(value.trim().to_owned()).filter(|it| !it.is_empty());
+
+                if let Some(config_handler) = self.config_handler.as_ref() {
+                    if let Err(e) = self.config.set_bitcoin_explorer_url(
+                        config_handler,
+                        non_empty(&self.editing_bitcoin_explorer_url),
+                    ) {
+                        tracing::error!("Error set_bitcoin_explorer_url: {e}");
+                    }
+                    if let Err(e) = self.config.set_evm_explorer_url(
+                        config_handler,
+                        non_empty(&self.editing_evm_explorer_url),
+                    ) {
+                        tracing::error!("Error set_evm_explorer_url: {e}");
+                    }
+                    if let Err(e) = self.config.set_evm_explorer_api_key(
+                        config_handler,
+                        non_empty(&self.editing_evm_explorer_api_key),
+                    ) {
+                        tracing::error!("Error set_evm_explorer_api_key: {e}");
+                    }
+                }
+            }
+            PaidEntriesPageMessage::ExcludeStableCoinsToggled(exclude_stable_coins_from_pnl) => {
+                if let Some(config_handler) = self.config_handler.as_ref() {
+                    if let Err(e) = self.config.set_exclude_stable_coins_from_pnl(
+                        config_handler,
+                        exclude_stable_coins_from_pnl,
+                    ) {
+                        tracing::error!("Error set_exclude_stable_coins_from_pnl: {e}");
+                    }
+                }
+            }
+            PaidEntriesPageMessage::DepegBandInput(depeg_band) => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let band_bps = depeg_band
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|percent| percent.is_finite() && *percent >= 0.0)
+                    .map(|percent| (percent * 100.0).round() as u32);
+
+                self.editing_depeg_band = depeg_band;
+
+                if let (Some(band_bps), Some(config_handler)) =
+                    (band_bps, self.config_handler.as_ref())
+                {
+                    if let Err(e) = self
+                        .config
+                        .set_stablecoin_depeg_band_bps(config_handler, Some(band_bps))
+                    {
+                        tracing::error!("Error set_stablecoin_depeg_band_bps: {e}");
+                    }
+                }
+            }
+            PaidEntriesPageMessage::FeeAllocationSelected(index) => {
+                if let Some(fee_allocation) = FeeAllocation::ALL.get(index) {
+                    if let Some(config_handler) = self.config_handler.as_ref() {
+                        if let Err(e) = self
+                            .config
+                            .set_fee_allocation(config_handler, *fee_allocation)
+                        {
+                            tracing::error!("Error set_fee_allocation: {e}");
+                        }
+                    }
+                }
+            }
+            PaidEntriesPageMessage::HoldingsSearchInput(search) => {
+                self.editing_holdings_search = search.clone();
+
+                return Task::future(async move {
+                    tokio::time::sleep(HOLDINGS_SEARCH_SAVE_DELAY).await;
+
+                    PaidEntriesPageMessage::HoldingsSearchSettled(search)
+                });
+            }
+            PaidEntriesPageMessage::HoldingsSearchSettled(search) => {
+                // Only the last of the keystrokes is saved
+                if search == self.editing_holdings_search
+                    && search != self.config.holdings_filter.search
+                {
+                    self.set_holdings_filter(HoldingsFilter {
+                        search,
+                        ..self.config.holdings_filter.clone()
+                    });
+                }
+            }
+            PaidEntriesPageMessage::HoldingsPlatformSelected(index) => {
+                if let Some(platform) = self.platform_filters.get(index) {
+                    self.set_holdings_filter(HoldingsFilter {
+                        platform: platform.clone(),
+                        ..self.config.holdings_filter.clone()
+                    });
+                }
+            }
+            PaidEntriesPageMessage::HoldingsStableCoinFilterSelected(index) => {
+                if let Some(stable_coins) = StableCoinFilter::ALL.get(index) {
+                    self.set_holdings_filter(HoldingsFilter {
+                        stable_coins: *stable_coins,
+                        ..self.config.holdings_filter.clone()
+                    });
+                }
+            }
+            PaidEntriesPageMessage::HoldingsProfitFilterSelected(index) => {
+                if let Some(profit) = ProfitFilter::ALL.get(index) {
+                    self.set_holdings_filter(HoldingsFilter {
+                        profit: *profit,
+                        ..self.config.holdings_filter.clone()
+                    });
+                }
+            }
+            PaidEntriesPageMessage::HoldingsDustThresholdInput(dust_threshold) => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let dust_threshold_cents = if dust_threshold.trim().is_empty() {
+                    Some(0)
+                } else {
+                    dust_threshold
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|usd| usd.is_finite() && *usd >= 0.0)
+                        .map(|usd| (usd * 100.0).round() as u64)
+                };
+
+                self.editing_dust_threshold = dust_threshold;
+
+                if let Some(dust_threshold_cents) = dust_threshold_cents {
+                    self.set_holdings_filter(HoldingsFilter {
+                        dust_threshold_cents,
+                        ..self.config.holdings_filter.clone()
+                    });
+                }
+            }
+            PaidEntriesPageMessage::HoldingsGroupByPlatformToggled(group_by_platform) => {
+                self.set_holdings_filter(HoldingsFilter {
+                    group_by_platform,
+                    ..self.config.holdings_filter.clone()
+                });
+            }
         }
 
         Task::none()
@@ -387,7 +1933,9 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
         let load_paid_entries_task = match self.paid_entries_json_load_state {
             PaidEntriesJsonLoadState::Errored | PaidEntriesJsonLoadState::Loaded => Task::none(),
             PaidEntriesJsonLoadState::NotLoaded => {
-                if let Some(json_path) = self.config.paid_entries_json_path.as_ref() {
+                if self.config.demo_mode {
+                    Self::load_demo_paid_entries()
+                } else if let Some(json_path) = self.config.paid_entries_json_path.as_ref() {
                     Self::load_paid_entries_json(json_path.clone())
                 } else {
                     cosmic::Task::none()
@@ -399,7 +1947,7 @@ impl pages::IPage<PaidEntriesPageMessage> for PaidEntriesPage {
             self.config.coin_market_cap_api_key.clone(),
             self.raw_json_data.clone(),
         ) {
-            (Some(api_key), Some(json_data)) => {
+            (Some(api_key), Some(json_data)) if !self.config.demo_mode => {
                 let symbols = json_data
                     .keys()
                     .map(std::borrow::ToOwned::to_owned)
@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use super::prices::{self, HistoricalPrices};
+use super::RawJsonData;
+
+/// What every buy could have gone into instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchmarkKind {
+    Portfolio,
+    Btc,
+    Eth,
+    Fiat,
+}
+
+impl BenchmarkKind {
+    pub const ALL: [BenchmarkKind; 4] = [
+        BenchmarkKind::Portfolio,
+        BenchmarkKind::Btc,
+        BenchmarkKind::Eth,
+        BenchmarkKind::Fiat,
+    ];
+
+    /// The coins whose history this benchmark needs, besides the ones of the portfolio.
+    #[must_use]
+    pub fn symbol(self) -> Option<&'static str> {
+        match self {
+            BenchmarkKind::Btc => Some("BTC"),
+            BenchmarkKind::Eth => Some("ETH"),
+            BenchmarkKind::Portfolio | BenchmarkKind::Fiat => None,
+        }
+    }
+}
+
+/// A dated buy, in USD.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashFlow {
+    pub date: NaiveDate,
+    pub symbol: String,
+    pub amount: f64,
+    pub amount_usd: f64,
+}
+
+/// Every dated buy of the paid entries, oldest first. Entries without a readable date
+/// can't be placed in time and are left out. Fiat fees are money put in, fees in the
+/// bought coin come out of what was received.
+#[must_use]
+pub fn cash_flows(raw_json_data: &RawJsonData) -> Vec<CashFlow> {
+    let mut cash_flows: Vec<CashFlow> = raw_json_data
+        .iter()
+        .flat_map(|(symbol, entries)| {
+            entries.iter().filter_map(|entry| {
+                let (fee_amount, fiat_fee_usd) = entry.fee.as_ref().map_or((0.0, 0.0), |fee| {
+                    let fiat_fee_usd = if fee.is_in_fiat() { fee.amount } else { 0.0 };
+                    (fee.amount_of(symbol), fiat_fee_usd)
+                });
+
+                Some(CashFlow {
+                    date: entry.date()?,
+                    symbol: symbol.clone(),
+                    amount: entry.amount - fee_amount,
+                    amount_usd: entry.amount_usd + fiat_fee_usd,
+                })
+            })
+        })
+        .collect();
+
+    cash_flows.sort_by_key(|cash_flow| cash_flow.date);
+    cash_flows
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    pub invested_usd: f64,
+    pub value_usd: f64,
+    /// Compounded over the whole period, cash flows excluded.
+    pub time_weighted_return: f64,
+    /// Annualized internal rate of return of the cash flows.
+    pub money_weighted_return: Option<f64>,
+}
+
+/// Prices a benchmark's holdings, either from history or from the latest quotes.
+struct Pricer<'a> {
+    history: &'a HistoricalPrices,
+    latest: &'a HashMap<String, f64>,
+}
+
+impl Pricer<'_> {
+    fn price(&self, symbol: &str, date: Option<NaiveDate>) -> Option<f64> {
+        if symbol == FIAT_SYMBOL {
+            return Some(1.0);
+        }
+
+        let history = self.history.get(symbol);
+
+        match date {
+            Some(date) => history.and_then(|history| prices::price_on(history, date)),
+            None => self.latest.get(symbol).copied().or_else(|| {
+                history.and_then(|history| history.last_key_value().map(|(_, price)| *price))
+            }),
+        }
+    }
+
+    /// `date` of `None` values the holdings at the latest prices.
+    fn value(&self, holdings: &HashMap<String, f64>, date: Option<NaiveDate>) -> Option<f64> {
+        holdings
+            .iter()
+            .map(|(symbol, amount)| Some(amount * self.price(symbol, date)?))
+            .sum()
+    }
+}
+
+const FIAT_SYMBOL: &str = "USD";
+
+/// Replays `cash_flows` into each [`BenchmarkKind`], `None` where a needed price is missing.
+#[must_use]
+pub fn benchmarks(
+    cash_flows: &[CashFlow],
+    history: &HistoricalPrices,
+    latest: &HashMap<String, f64>,
+    today: NaiveDate,
+) -> Vec<(BenchmarkKind, Option<Benchmark>)> {
+    let pricer = Pricer { history, latest };
+
+    BenchmarkKind::ALL
+        .into_iter()
+        .map(|kind| (kind, benchmark(kind, cash_flows, &pricer, today)))
+        .collect()
+}
+
+fn benchmark(
+    kind: BenchmarkKind,
+    cash_flows: &[CashFlow],
+    pricer: &Pricer,
+    today: NaiveDate,
+) -> Option<Benchmark> {
+    let mut holdings: HashMap<String, f64> = HashMap::new();
+    let mut invested_usd = 0.0;
+    let mut growth = 1.0;
+    let mut value_after_last_flow = 0.0;
+
+    for (index, cash_flow) in cash_flows.iter().enumerate() {
+        if index > 0 && cash_flows[index - 1].date != cash_flow.date {
+            let value_before_flow = pricer.value(&holdings, Some(cash_flow.date))?;
+            if value_after_last_flow > 0.0 {
+                growth *= value_before_flow / value_after_last_flow;
+            }
+        }
+
+        let (symbol, amount) = match kind {
+            BenchmarkKind::Portfolio => (cash_flow.symbol.as_str(), cash_flow.amount),
+            BenchmarkKind::Fiat => (FIAT_SYMBOL, cash_flow.amount_usd),
+            BenchmarkKind::Btc | BenchmarkKind::Eth => {
+                let symbol = kind.symbol()?;
+                let price = pricer.price(symbol, Some(cash_flow.date))?;
+
+                (symbol, cash_flow.amount_usd / price)
+            }
+        };
+
+        *holdings.entry(symbol.to_owned()).or_default() += amount;
+        invested_usd += cash_flow.amount_usd;
+        value_after_last_flow = pricer.value(&holdings, Some(cash_flow.date))?;
+    }
+
+    let value_usd = pricer.value(&holdings, None)?;
+    if value_after_last_flow > 0.0 {
+        growth *= value_usd / value_after_last_flow;
+    }
+
+    Some(Benchmark {
+        invested_usd,
+        value_usd,
+        time_weighted_return: growth - 1.0,
+        money_weighted_return: money_weighted_return(cash_flows, value_usd, today),
+    })
+}
+
+/// Solves for the yearly rate that brings the net present value of the buys and the
+/// current value to zero, by bisection.
+#[allow(clippy::cast_precision_loss)]
+fn money_weighted_return(cash_flows: &[CashFlow], value_usd: f64, today: NaiveDate) -> Option<f64> {
+    let first_date = cash_flows.first()?.date;
+    let years_since_start = |date: NaiveDate| (date - first_date).num_days() as f64 / 365.0;
+    let net_present_value = |rate: f64| {
+        value_usd / (1.0 + rate).powf(years_since_start(today))
+            - cash_flows
+                .iter()
+                .map(|it| it.amount_usd / (1.0 + rate).powf(years_since_start(it.date)))
+                .sum::<f64>()
+    };
+
+    let (mut low, mut high) = (-0.9999, 1000.0);
+    if net_present_value(low).signum() == net_present_value(high).signum() {
+        return None;
+    }
+
+    for _ in 0..200 {
+        let mid = f64::midpoint(low, high);
+
+        if net_present_value(mid).signum() == net_present_value(low).signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Some(f64::midpoint(low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+
+    use super::super::prices::{HistoricalPrices, PriceHistory};
+    use super::{benchmarks, money_weighted_return, Benchmark, BenchmarkKind, CashFlow};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn cash_flow(date: NaiveDate, symbol: &str, amount: f64, amount_usd: f64) -> CashFlow {
+        CashFlow {
+            date,
+            symbol: symbol.to_owned(),
+            amount,
+            amount_usd,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{actual} is not {expected}"
+        );
+    }
+
+    /// A BTC buy on New Year, then more BTC and some ETH once BTC doubled.
+    fn replay(kind: BenchmarkKind) -> Option<Benchmark> {
+        let (start, middle) = (date(2025, 1, 1), date(2025, 7, 1));
+        let cash_flows = [
+            cash_flow(start, "BTC", 1.0, 100.0),
+            cash_flow(middle, "BTC", 0.5, 100.0),
+            cash_flow(middle, "ETH", 1.0, 50.0),
+        ];
+        let history = HistoricalPrices::from([
+            (
+                "BTC".to_owned(),
+                PriceHistory::from([(start, 100.0), (middle, 200.0)]),
+            ),
+            ("ETH".to_owned(), PriceHistory::from([(middle, 50.0)])),
+        ]);
+        let latest = HashMap::from([("BTC".to_owned(), 300.0), ("ETH".to_owned(), 25.0)]);
+
+        benchmarks(&cash_flows, &history, &latest, date(2026, 1, 1))
+            .into_iter()
+            .find_map(|(it, benchmark)| (it == kind).then_some(benchmark))
+            .unwrap()
+    }
+
+    #[test]
+    fn replays_the_portfolio() {
+        let benchmark = replay(BenchmarkKind::Portfolio).unwrap();
+
+        // 1.5 BTC at 300 and 1 ETH at 25.
+        assert_close(benchmark.invested_usd, 250.0);
+        assert_close(benchmark.value_usd, 475.0);
+        // 100 -> 200 until the second buys, then 350 -> 475.
+        assert_close(benchmark.time_weighted_return, 2.0 * 475.0 / 350.0 - 1.0);
+    }
+
+    #[test]
+    fn replays_every_buy_into_a_single_coin() {
+        // 1 BTC, then 150 USD at 200 for 0.75 more.
+        let btc = replay(BenchmarkKind::Btc).unwrap();
+        assert_close(btc.value_usd, 1.75 * 300.0);
+        assert_close(btc.time_weighted_return, 2.0 * 1.5 - 1.0);
+
+        // The first buy is priced at the first close after it, 2 ETH, then 3 more.
+        let eth = replay(BenchmarkKind::Eth).unwrap();
+        assert_close(eth.value_usd, 5.0 * 25.0);
+        assert_close(eth.time_weighted_return, -0.5);
+    }
+
+    #[test]
+    fn fiat_neither_gains_nor_loses() {
+        let fiat = replay(BenchmarkKind::Fiat).unwrap();
+
+        assert_close(fiat.value_usd, 250.0);
+        assert_close(fiat.time_weighted_return, 0.0);
+        assert_close(fiat.money_weighted_return.unwrap(), 0.0);
+    }
+
+    #[test]
+    fn leaves_out_benchmarks_missing_a_price() {
+        let cash_flows = [cash_flow(date(2025, 1, 1), "XYZ", 10.0, 100.0)];
+        let benchmarks = benchmarks(
+            &cash_flows,
+            &HistoricalPrices::new(),
+            &HashMap::new(),
+            date(2026, 1, 1),
+        );
+
+        assert_eq!(
+            benchmarks
+                .iter()
+                .map(|(kind, benchmark)| (*kind, benchmark.is_some()))
+                .collect::<Vec<_>>(),
+            [
+                (BenchmarkKind::Portfolio, false),
+                (BenchmarkKind::Btc, false),
+                (BenchmarkKind::Eth, false),
+                (BenchmarkKind::Fiat, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn solves_the_money_weighted_return() {
+        // 100 grown by 10% a year for two years, and 100 more for the second one.
+        let cash_flows = [
+            cash_flow(date(2025, 1, 1), "BTC", 1.0, 100.0),
+            cash_flow(date(2026, 1, 1), "BTC", 1.0, 100.0),
+        ];
+        let value_usd = 100.0 * 1.1 * 1.1 + 100.0 * 1.1;
+
+        assert_close(
+            money_weighted_return(&cash_flows, value_usd, date(2027, 1, 1)).unwrap(),
+            0.1,
+        );
+        assert_close(
+            money_weighted_return(&cash_flows[..1], 50.0, date(2026, 1, 1)).unwrap(),
+            -0.5,
+        );
+    }
+
+    #[test]
+    fn gives_up_on_rates_out_of_range() {
+        let cash_flows = [cash_flow(date(2025, 1, 1), "BTC", 1.0, 100.0)];
+
+        // Nothing left: no rate above -100% a year brings the buys back to zero.
+        assert_eq!(
+            money_weighted_return(&cash_flows, 0.0, date(2026, 1, 1)),
+            None
+        );
+        assert_eq!(money_weighted_return(&[], 100.0, date(2026, 1, 1)), None);
+    }
+}
@@ -0,0 +1,403 @@
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::portfolio::CoinHolding;
+use super::BuyEntry;
+
+/// How many months ahead the DCA projection looks.
+pub const PROJECTION_MONTHS: u32 = 12;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DcaFrequency {
+    Daily,
+    Weekly,
+    Biweekly,
+    #[default]
+    Monthly,
+}
+
+impl DcaFrequency {
+    pub const ALL: [DcaFrequency; 4] = [
+        DcaFrequency::Daily,
+        DcaFrequency::Weekly,
+        DcaFrequency::Biweekly,
+        DcaFrequency::Monthly,
+    ];
+
+    /// The `nth` buy of a plan starting on `start`, counted from the start so monthly
+    /// plans don't drift after a short month.
+    #[must_use]
+    pub fn nth_date(self, start: NaiveDate, nth: u32) -> Option<NaiveDate> {
+        match self {
+            DcaFrequency::Daily => start.checked_add_days(Days::new(u64::from(nth))),
+            DcaFrequency::Weekly => start.checked_add_days(Days::new(7 * u64::from(nth))),
+            DcaFrequency::Biweekly => start.checked_add_days(Days::new(14 * u64::from(nth))),
+            DcaFrequency::Monthly => start.checked_add_months(Months::new(nth)),
+        }
+    }
+}
+
+/// A recurring buy of a fixed amount of `currency` into `symbol`.
+///
+/// The amount is kept in cents so the plan stays `Eq` inside `UniConfig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DcaPlan {
+    pub symbol: String,
+    pub amount_cents: u64,
+    pub currency: String,
+    pub frequency: DcaFrequency,
+    pub start_date: NaiveDate,
+}
+
+impl DcaPlan {
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn amount(&self) -> f64 {
+        self.amount_cents as f64 / 100.0
+    }
+
+    /// Projections only make sense when the plan is in the currency of the fetched quotes.
+    #[must_use]
+    pub fn is_in_usd(&self) -> bool {
+        self.currency.eq_ignore_ascii_case("USD")
+    }
+
+    /// Scheduled buys from the start of the plan up to the first one after `today`.
+    #[must_use]
+    pub fn schedule(&self, entries: &[BuyEntry], today: NaiveDate) -> Vec<ScheduledBuy> {
+        let mut scheduled_buys = vec![];
+        let mut nth = 0;
+
+        while let Some(date) = self.frequency.nth_date(self.start_date, nth) {
+            let next_date = self.frequency.nth_date(self.start_date, nth + 1);
+
+            if date > today {
+                scheduled_buys.push(ScheduledBuy {
+                    date,
+                    status: DcaBuyStatus::Upcoming,
+                });
+                break;
+            }
+
+            let is_bought = entries.iter().filter_map(BuyEntry::date).any(|entry_date| {
+                entry_date >= date && next_date.is_none_or(|next_date| entry_date < next_date)
+            });
+            let is_current = next_date.is_none_or(|next_date| today < next_date);
+
+            scheduled_buys.push(ScheduledBuy {
+                date,
+                status: match (is_bought, is_current) {
+                    (true, _) => DcaBuyStatus::Done,
+                    (false, true) => DcaBuyStatus::Due,
+                    (false, false) => DcaBuyStatus::Overdue,
+                },
+            });
+
+            nth += 1;
+        }
+
+        scheduled_buys
+    }
+
+    /// Holdings and cost after [`PROJECTION_MONTHS`] of buys, for every [`PriceScenario`].
+    #[must_use]
+    pub fn project(
+        &self,
+        holding: &CoinHolding,
+        current_price: f64,
+        today: NaiveDate,
+    ) -> Vec<DcaProjection> {
+        let Some(end_date) = today.checked_add_months(Months::new(PROJECTION_MONTHS)) else {
+            return vec![];
+        };
+
+        let buy_dates: Vec<NaiveDate> = (0..)
+            .map_while(|nth| self.frequency.nth_date(self.start_date, nth))
+            .skip_while(|date| *date <= today)
+            .take_while(|date| *date <= end_date)
+            .collect();
+
+        PriceScenario::ALL
+            .into_iter()
+            .map(|scenario| {
+                let mut projection = DcaProjection {
+                    scenario,
+                    buys: 0,
+                    total_cost_usd: holding.cost_usd,
+                    holding_amount: holding.amount,
+                    value_usd: 0.0,
+                };
+
+                for date in &buy_dates {
+                    let price = scenario.price_at(current_price, today, *date);
+
+                    if price > 0.0 {
+                        projection.buys += 1;
+                        projection.total_cost_usd += self.amount();
+                        projection.holding_amount += self.amount() / price;
+                    }
+                }
+
+                projection.value_usd =
+                    projection.holding_amount * scenario.price_at(current_price, today, end_date);
+                projection
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcaBuyStatus {
+    Done,
+    Due,
+    Overdue,
+    Upcoming,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledBuy {
+    pub date: NaiveDate,
+    pub status: DcaBuyStatus,
+}
+
+/// Yearly price change assumed by a projection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceScenario {
+    Bear,
+    Flat,
+    Bull,
+}
+
+impl PriceScenario {
+    pub const ALL: [PriceScenario; 3] = [
+        PriceScenario::Bear,
+        PriceScenario::Flat,
+        PriceScenario::Bull,
+    ];
+
+    #[must_use]
+    pub fn yearly_change_percent(self) -> i32 {
+        match self {
+            PriceScenario::Bear => -50,
+            PriceScenario::Flat => 0,
+            PriceScenario::Bull => 100,
+        }
+    }
+
+    /// Compounds the yearly change day by day from `today`.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn price_at(self, current_price: f64, today: NaiveDate, date: NaiveDate) -> f64 {
+        let years = (date - today).num_days() as f64 / 365.0;
+        let yearly_factor = 1.0 + f64::from(self.yearly_change_percent()) / 100.0;
+
+        current_price * yearly_factor.powf(years)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcaProjection {
+    pub scenario: PriceScenario,
+    pub buys: u32,
+    pub total_cost_usd: f64,
+    pub holding_amount: f64,
+    pub value_usd: f64,
+}
+
+/// The "add plan" inputs, kept as typed text until submitted.
+#[derive(Debug, Default)]
+pub struct DcaPlanForm {
+    pub symbol: String,
+    pub amount: String,
+    pub currency: String,
+    pub frequency: DcaFrequency,
+    pub start_date: String,
+}
+
+impl DcaPlanForm {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_plan(&self, today: NaiveDate) -> Result<DcaPlan, DcaPlanFormError> {
+        let symbol = self.symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return Err(DcaPlanFormError::MissingSymbol);
+        }
+
+        let amount = self
+            .amount
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|amount| amount.is_finite() && *amount > 0.0)
+            .ok_or(DcaPlanFormError::InvalidAmount)?;
+
+        let start_date = if self.start_date.trim().is_empty() {
+            today
+        } else {
+            NaiveDate::parse_from_str(self.start_date.trim(), "%Y-%m-%d")
+                .map_err(|_| DcaPlanFormError::InvalidStartDate)?
+        };
+
+        let currency = self.currency.trim().to_uppercase();
+
+        Ok(DcaPlan {
+            symbol,
+            amount_cents: (amount * 100.0).round() as u64,
+            currency: if currency.is_empty() {
+                "USD".to_owned()
+            } else {
+                currency
+            },
+            frequency: self.frequency,
+            start_date,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcaPlanFormError {
+    MissingSymbol,
+    InvalidAmount,
+    InvalidStartDate,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::super::portfolio::CoinHolding;
+    use super::super::BuyEntry;
+    use super::{DcaBuyStatus, DcaFrequency, DcaPlan, PriceScenario, ScheduledBuy};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn plan(frequency: DcaFrequency, start_date: NaiveDate) -> DcaPlan {
+        DcaPlan {
+            symbol: "BTC".to_owned(),
+            amount_cents: 10_000,
+            currency: "USD".to_owned(),
+            frequency,
+            start_date,
+        }
+    }
+
+    fn buy(date: &str) -> BuyEntry {
+        BuyEntry {
+            is_stable_coin: None,
+            date: date.to_owned(),
+            amount_usd: 100.0,
+            amount: 0.001,
+            fee: None,
+        }
+    }
+
+    fn statuses(schedule: &[ScheduledBuy]) -> Vec<(NaiveDate, DcaBuyStatus)> {
+        schedule
+            .iter()
+            .map(|scheduled_buy| (scheduled_buy.date, scheduled_buy.status))
+            .collect()
+    }
+
+    #[test]
+    fn monthly_buys_keep_to_the_start_day() {
+        let frequency = DcaFrequency::Monthly;
+        let start = date(2025, 1, 31);
+
+        assert_eq!(frequency.nth_date(start, 1), Some(date(2025, 2, 28)));
+        assert_eq!(frequency.nth_date(start, 2), Some(date(2025, 3, 31)));
+        assert_eq!(
+            DcaFrequency::Biweekly.nth_date(start, 2),
+            Some(date(2025, 2, 28))
+        );
+    }
+
+    #[test]
+    fn schedules_up_to_the_next_buy() {
+        let plan = plan(DcaFrequency::Monthly, date(2025, 1, 31));
+        // Before the plan, then in the February period, an hour after midnight UTC.
+        let entries = [buy("2025-01-15"), buy("2025-03-01T01:00:00Z")];
+
+        assert_eq!(
+            statuses(&plan.schedule(&entries, date(2025, 4, 10))),
+            [
+                (date(2025, 1, 31), DcaBuyStatus::Overdue),
+                (date(2025, 2, 28), DcaBuyStatus::Done),
+                (date(2025, 3, 31), DcaBuyStatus::Due),
+                (date(2025, 4, 30), DcaBuyStatus::Upcoming),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_buy_is_due_until_the_next_one_and_overdue_after() {
+        let plan = plan(DcaFrequency::Weekly, date(2025, 1, 1));
+
+        assert_eq!(
+            statuses(&plan.schedule(&[], date(2025, 1, 7))),
+            [
+                (date(2025, 1, 1), DcaBuyStatus::Due),
+                (date(2025, 1, 8), DcaBuyStatus::Upcoming),
+            ]
+        );
+        assert_eq!(
+            statuses(&plan.schedule(&[], date(2025, 1, 8))),
+            [
+                (date(2025, 1, 1), DcaBuyStatus::Overdue),
+                (date(2025, 1, 8), DcaBuyStatus::Due),
+                (date(2025, 1, 15), DcaBuyStatus::Upcoming),
+            ]
+        );
+        assert_eq!(
+            statuses(&plan.schedule(&[], date(2024, 12, 1))),
+            [(date(2025, 1, 1), DcaBuyStatus::Upcoming)]
+        );
+    }
+
+    #[test]
+    fn projects_a_year_of_buys_from_the_holding() {
+        let plan = plan(DcaFrequency::Monthly, date(2025, 1, 1));
+        let holding = CoinHolding {
+            symbol: "BTC".to_owned(),
+            amount: 1.0,
+            cost_usd: 40.0,
+            fees_usd: 0.0,
+            expensed_fees_usd: 0.0,
+            unvalued_fees: 0,
+            is_stable_coin: false,
+        };
+
+        let projections = plan.project(&holding, 50.0, date(2025, 1, 1));
+
+        assert_eq!(
+            projections
+                .iter()
+                .map(|projection| (projection.scenario, projection.buys))
+                .collect::<Vec<_>>(),
+            [
+                (PriceScenario::Bear, 12),
+                (PriceScenario::Flat, 12),
+                (PriceScenario::Bull, 12),
+            ]
+        );
+
+        // Today's buy is left to the schedule, then February to January at 50.
+        let flat = &projections[1];
+        assert!((flat.total_cost_usd - 1240.0).abs() < 1e-9);
+        assert!((flat.holding_amount - 25.0).abs() < 1e-9);
+        assert!((flat.value_usd - 1250.0).abs() < 1e-9);
+
+        assert!(projections[0].holding_amount > flat.holding_amount);
+        assert!(projections[2].value_usd > flat.value_usd);
+    }
+
+    #[test]
+    fn compounds_the_yearly_change() {
+        let today = date(2025, 1, 1);
+        let in_a_year = date(2026, 1, 1);
+
+        assert!((PriceScenario::Bear.price_at(100.0, today, in_a_year) - 50.0).abs() < 1e-9);
+        assert!((PriceScenario::Bull.price_at(100.0, today, in_a_year) - 200.0).abs() < 1e-9);
+        assert!((PriceScenario::Flat.price_at(100.0, today, in_a_year) - 100.0).abs() < 1e-9);
+    }
+}
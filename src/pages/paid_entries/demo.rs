@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use rust_embed::RustEmbed;
+
+use super::prices::{self, HistoricalPrices};
+use super::{parse_paid_entries, CoinApiRecord, RawJsonData};
+
+/// A sample portfolio and the CMC responses that price it, so the page renders
+/// without an API key or a JSON file of one's own.
+#[derive(RustEmbed)]
+#[folder = "resources/demo/"]
+struct DemoFixtures;
+
+const PAID_ENTRIES: &str = "paid_entries.json";
+const LATEST_QUOTES: &str = "cmc_quotes_latest.json";
+const HISTORICAL_QUOTES: &str = "cmc_quotes_historical.json";
+
+fn fixture(path: &str) -> Result<Vec<u8>, String> {
+    DemoFixtures::get(path)
+        .map(|file| file.data.into_owned())
+        .ok_or_else(|| format!("missing demo fixture `{path}`"))
+}
+
+pub fn paid_entries() -> Result<RawJsonData, String> {
+    let raw_json = String::from_utf8(fixture(PAID_ENTRIES)?).map_err(|e| e.to_string())?;
+
+    parse_paid_entries(&raw_json).map_err(|e| e.to_string())
+}
+
+pub fn latest_quotes() -> Result<HashMap<String, Vec<CoinApiRecord>>, String> {
+    prices::parse_latest_quotes(&fixture(LATEST_QUOTES)?).map_err(|e| e.to_string())
+}
+
+pub fn historical_prices() -> Result<HistoricalPrices, String> {
+    prices::parse_daily_history(&fixture(HISTORICAL_QUOTES)?).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::super::benchmark::{self, BenchmarkKind};
+    use super::super::fees::FeeAllocation;
+    use super::super::filters::{self, PlatformFilter};
+    use super::super::ledger::{Journal, LedgerFormat};
+    use super::super::portfolio::{self, PortfolioSummary};
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn fixtures_parse() {
+        let raw_json_data = paid_entries().unwrap();
+        let quotes = latest_quotes().unwrap();
+        let history = historical_prices().unwrap();
+
+        assert_eq!(raw_json_data.len(), 6);
+        for symbol in raw_json_data.keys() {
+            assert!(quotes.contains_key(symbol), "no quote for {symbol}");
+            assert!(history.contains_key(symbol), "no history for {symbol}");
+        }
+        assert_eq!(portfolio::latest_usd_price(&quotes, "BTC"), Some(97_250.0));
+        assert_eq!(history["BTC"].len(), 18);
+        assert_eq!(
+            prices::price_on(
+                &history["ETH"],
+                NaiveDate::from_ymd_opt(2024, 6, 15).unwrap()
+            ),
+            Some(3760.0)
+        );
+    }
+
+    #[test]
+    fn holdings_take_fees_into_account() {
+        let holdings = portfolio::holdings(&paid_entries().unwrap(), FeeAllocation::Capitalize);
+        let holding = |symbol: &str| {
+            holdings
+                .iter()
+                .find(|holding| holding.symbol == symbol)
+                .unwrap()
+        };
+
+        let btc = holding("BTC");
+        assert_close(btc.amount, 0.105_35);
+        // The fee paid in BTC is already out of the amount
+        assert_close(btc.cost_usd, 6004.0);
+
+        // Gas for UNI and LINK was paid in ETH
+        let eth = holding("ETH");
+        assert_close(eth.amount, 0.92 - 0.002 - 0.0015);
+        assert_close(eth.cost_usd, 2602.5 * (1.0 - 0.0035 / 0.92));
+        assert_close(holding("UNI").cost_usd, 307.4);
+
+        assert!(holding("USDC").is_stable_coin);
+        assert!(!holding("SOL").is_stable_coin);
+    }
+
+    #[test]
+    fn expensed_fees_stay_out_of_cost_basis() {
+        let raw_json_data = paid_entries().unwrap();
+        let quotes = latest_quotes().unwrap();
+        let capitalized = PortfolioSummary::new(
+            &portfolio::holdings(&raw_json_data, FeeAllocation::Capitalize),
+            &quotes,
+            true,
+        );
+        let expensed = PortfolioSummary::new(
+            &portfolio::holdings(&raw_json_data, FeeAllocation::Expense),
+            &quotes,
+            true,
+        );
+
+        assert_close(capitalized.cash_like_usd, 999.8);
+        assert_close(capitalized.fees_usd, expensed.fees_usd);
+        assert_eq!(capitalized.unvalued_fees, 0);
+
+        // The USD fees on BTC and ETH and the gas for UNI and LINK were paid on top
+        assert_close(capitalized.expensed_fees_usd, 0.0);
+        assert_close(expensed.expensed_fees_usd, 4.0 + 2.5 + 7.4 + 5.1);
+        // The share of ETH, and of its basis, that wasn't spent on gas
+        let eth_kept = 1.0 - 0.0035 / 0.92;
+        assert_close(
+            capitalized.cost_usd - expensed.cost_usd,
+            4.0 + 2.5 * eth_kept + 7.4 + 5.1,
+        );
+
+        // Fees come off P&L once, through cost basis or as expenses
+        let value_usd =
+            0.105_35 * 97_250.0 + 0.9165 * 3420.5 + 5.9 * 182.3 + 30.0 * 9.85 + 18.5 * 17.4;
+        assert_close(capitalized.value_usd, value_usd);
+        assert_close(
+            capitalized.pnl_usd(),
+            value_usd - (6004.0 + 2602.5 * eth_kept + 900.0 + 307.4 + 255.1),
+        );
+        assert_close(
+            expensed.pnl_usd(),
+            value_usd - (6000.0 + 2600.0 * eth_kept + 900.0 + 300.0 + 250.0) - 19.0,
+        );
+    }
+
+    #[test]
+    fn fees_without_a_value_are_flagged() {
+        let raw_json_data = parse_paid_entries(
+            r#"{"UNI": [{"date": "2024-05-20", "amountUsd": 300.0, "amount": 30.0,
+                "fee": {"amount": 0.002, "asset": "ETH"}}]}"#,
+        )
+        .unwrap();
+
+        for fee_allocation in FeeAllocation::ALL {
+            let holdings = portfolio::holdings(&raw_json_data, fee_allocation);
+
+            assert_eq!(holdings[0].unvalued_fees, 1);
+            assert_close(holdings[0].cost_usd, 300.0);
+            assert_close(holdings[0].pnl_usd(9.85), 295.5 - 300.0);
+        }
+    }
+
+    #[test]
+    fn excluded_holdings_keep_their_fees_out_of_pnl() {
+        let raw_json_data = parse_paid_entries(
+            r#"{"BTC": [{"date": "2024-03-01", "amountUsd": 5000.0, "amount": 0.1}],
+                "USDC": [{"date": "2024-03-01", "amountUsd": 1000.0, "amount": 1000.0,
+                    "fee": {"amount": 1.5, "asset": "USD"}}],
+                "XYZ": [{"date": "2024-03-01", "amountUsd": 50.0, "amount": 10.0,
+                    "fee": {"amount": 0.5, "asset": "USD"}}]}"#,
+        )
+        .unwrap();
+        let quotes = latest_quotes().unwrap();
+        let holdings = portfolio::holdings(&raw_json_data, FeeAllocation::Expense);
+
+        // Neither the excluded USDC nor the unpriced XYZ count towards P&L
+        let excluded = PortfolioSummary::new(&holdings, &quotes, true);
+        assert_close(excluded.fees_usd, 2.0);
+        assert_close(excluded.expensed_fees_usd, 0.0);
+        assert_close(excluded.pnl_usd(), 0.1 * 97_250.0 - 5000.0);
+
+        let included = PortfolioSummary::new(&holdings, &quotes, false);
+        assert_close(included.expensed_fees_usd, 1.5);
+        assert_close(
+            included.pnl_usd(),
+            0.1 * 97_250.0 + 1000.0 * 0.9998 - 6000.0 - 1.5,
+        );
+    }
+
+    #[test]
+    fn platforms_come_from_the_quotes() {
+        let quotes = latest_quotes().unwrap();
+
+        assert_eq!(
+            filters::platform_of(quotes.get("BTC")),
+            PlatformFilter::Native
+        );
+        assert_eq!(
+            filters::platform_of(quotes.get("UNI")),
+            PlatformFilter::Named("Ethereum".to_owned())
+        );
+    }
+
+    #[test]
+    fn benchmarks_cover_every_kind() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let quotes = latest_quotes().unwrap();
+        let latest = quotes
+            .keys()
+            .filter_map(|symbol| {
+                Some((
+                    symbol.clone(),
+                    portfolio::latest_usd_price(&quotes, symbol)?,
+                ))
+            })
+            .collect();
+        let benchmarks = benchmark::benchmarks(
+            &benchmark::cash_flows(&paid_entries().unwrap()),
+            &historical_prices().unwrap(),
+            &latest,
+            today,
+        );
+
+        assert_eq!(benchmarks.len(), BenchmarkKind::ALL.len());
+        for (kind, benchmark) in benchmarks {
+            let benchmark = benchmark.unwrap_or_else(|| panic!("no {kind:?} benchmark"));
+            assert_close(benchmark.invested_usd, 11_056.5);
+        }
+    }
+
+    #[test]
+    fn journals_round_trip() {
+        let raw_json_data = paid_entries().unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let journal = Journal::from_entries(&raw_json_data, None, today);
+
+        for format in [LedgerFormat::Beancount, LedgerFormat::Hledger] {
+            let parsed = Journal::parse(&journal.render(format).unwrap(), format);
+
+            assert_eq!(parsed.skipped_postings, []);
+            assert_eq!(parsed.transactions.len(), 12);
+            assert_eq!(parsed.commodities, journal.commodities);
+            for (parsed, original) in parsed.transactions.iter().zip(&journal.transactions) {
+                assert_eq!(parsed.symbol, original.symbol);
+                assert_close(parsed.amount, original.amount);
+                assert_close(parsed.cost_usd, original.cost_usd);
+
+                let (Some(parsed_fee), Some(original_fee)) = (&parsed.fee, &original.fee) else {
+                    assert_eq!(parsed.fee, original.fee);
+                    continue;
+                };
+                assert_eq!(parsed_fee.asset, original_fee.asset);
+                assert_close(parsed_fee.amount, original_fee.amount);
+                if let Some(amount_usd) = original_fee.amount_usd {
+                    assert_close(parsed_fee.amount_usd.unwrap(), amount_usd);
+                }
+            }
+            assert_eq!(
+                parsed
+                    .transactions
+                    .iter()
+                    .filter(|it| it.fee.is_some())
+                    .count(),
+                5
+            );
+        }
+    }
+}
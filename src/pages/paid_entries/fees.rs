@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::BuyEntry;
+
+/// The asset fiat fees are recorded in.
+pub const FIAT_ASSET: &str = "USD";
+
+/// What a transaction paid on top of the trade: in fiat, in the traded coin or in a
+/// third asset, such as gas paid in ETH.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Fee {
+    pub amount: f64,
+    pub asset: String,
+    /// What the fee was worth when paid. Fees in a third asset need it to count towards
+    /// cost basis, the others are valued from the entry itself.
+    #[serde(rename = "amountUsd", default, skip_serializing_if = "Option::is_none")]
+    pub amount_usd: Option<f64>,
+}
+
+impl Fee {
+    #[must_use]
+    pub fn is_in(&self, asset: &str) -> bool {
+        self.asset.eq_ignore_ascii_case(asset)
+    }
+
+    #[must_use]
+    pub fn is_in_fiat(&self) -> bool {
+        self.is_in(FIAT_ASSET)
+    }
+
+    /// A fee in the traded coin is valued at the price of the trade it was paid on.
+    /// `None` for a fee in a third asset that wasn't recorded with its value.
+    #[must_use]
+    pub fn value_usd(&self, symbol: &str, entry: &BuyEntry) -> Option<f64> {
+        if let Some(amount_usd) = self.amount_usd {
+            Some(amount_usd)
+        } else if self.is_in_fiat() {
+            Some(self.amount)
+        } else if self.is_in(symbol) && entry.amount > 0.0 {
+            Some(entry.amount_usd / entry.amount * self.amount)
+        } else {
+            None
+        }
+    }
+
+    /// How much of `symbol` the fee took out of what the entry bought.
+    #[must_use]
+    pub fn amount_of(&self, symbol: &str) -> f64 {
+        if self.is_in(symbol) {
+            self.amount
+        } else {
+            0.0
+        }
+    }
+
+    /// A fee paid in neither fiat nor `symbol`, which spends another holding.
+    #[must_use]
+    pub fn is_in_third_asset(&self, symbol: &str) -> bool {
+        !self.is_in_fiat() && !self.is_in(symbol)
+    }
+}
+
+/// How fees are reflected in the cost basis of what they were paid for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeAllocation {
+    /// Fees add to the cost basis of the bought coin, as most tax rules want.
+    #[default]
+    Capitalize,
+    /// Cost basis stays at the trade price, fees are counted as a separate expense.
+    Expense,
+}
+
+impl FeeAllocation {
+    pub const ALL: [FeeAllocation; 2] = [FeeAllocation::Capitalize, FeeAllocation::Expense];
+}
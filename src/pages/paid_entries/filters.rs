@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use super::portfolio::CoinHolding;
+use super::CoinApiRecord;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StableCoinFilter {
+    #[default]
+    All,
+    Only,
+    Exclude,
+}
+
+impl StableCoinFilter {
+    pub const ALL: [StableCoinFilter; 3] = [
+        StableCoinFilter::All,
+        StableCoinFilter::Only,
+        StableCoinFilter::Exclude,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProfitFilter {
+    #[default]
+    All,
+    InProfit,
+    AtLoss,
+}
+
+impl ProfitFilter {
+    pub const ALL: [ProfitFilter; 3] = [
+        ProfitFilter::All,
+        ProfitFilter::InProfit,
+        ProfitFilter::AtLoss,
+    ];
+}
+
+/// Which platform a holding lives on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PlatformFilter {
+    /// Coins with their own chain, which CMC reports without a platform.
+    Native,
+    Named(String),
+}
+
+/// The Coin Balance tab's search, filters and grouping, persisted in `UniConfig`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldingsFilter {
+    pub search: String,
+    /// `None` shows every platform.
+    pub platform: Option<PlatformFilter>,
+    pub stable_coins: StableCoinFilter,
+    pub profit: ProfitFilter,
+    /// Holdings worth less than this are hidden, `0` shows them all.
+    pub dust_threshold_cents: u64,
+    pub group_by_platform: bool,
+}
+
+/// The platform of a coin, from its first CMC record.
+#[must_use]
+pub fn platform_of(records: Option<&Vec<CoinApiRecord>>) -> PlatformFilter {
+    records
+        .and_then(|records| records.first())
+        .and_then(|record| record.platform.as_ref())
+        .map_or(PlatformFilter::Native, |platform| {
+            PlatformFilter::Named(platform.name.clone())
+        })
+}
+
+impl HoldingsFilter {
+    /// `price` and `records` are `None` until quotes are fetched, in which case only
+    /// the search and the stablecoin filter apply.
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn matches(
+        &self,
+        holding: &CoinHolding,
+        price: Option<f64>,
+        records: Option<&Vec<CoinApiRecord>>,
+    ) -> bool {
+        let search = self.search.trim().to_lowercase();
+        if !search.is_empty() {
+            let name = records
+                .and_then(|records| records.first())
+                .map(|record| record.name.to_lowercase())
+                .unwrap_or_default();
+
+            if !holding.symbol.to_lowercase().contains(&search) && !name.contains(&search) {
+                return false;
+            }
+        }
+
+        let is_stable_coin_shown = match self.stable_coins {
+            StableCoinFilter::All => true,
+            StableCoinFilter::Only => holding.is_stable_coin,
+            StableCoinFilter::Exclude => !holding.is_stable_coin,
+        };
+        if !is_stable_coin_shown {
+            return false;
+        }
+
+        if records.is_some() {
+            if let Some(platform) = &self.platform {
+                if *platform != platform_of(records) {
+                    return false;
+                }
+            }
+        }
+
+        let Some(price) = price else {
+            return true;
+        };
+
+        let is_profit_shown = match self.profit {
+            ProfitFilter::All => true,
+            ProfitFilter::InProfit => holding.pnl_usd(price) >= 0.0,
+            ProfitFilter::AtLoss => holding.pnl_usd(price) < 0.0,
+        };
+
+        is_profit_shown && holding.value_usd(price) >= self.dust_threshold_cents as f64 / 100.0
+    }
+}
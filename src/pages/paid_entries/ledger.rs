@@ -0,0 +1,653 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{self, Write as _};
+
+use chrono::NaiveDate;
+
+use super::fees::{Fee, FIAT_ASSET};
+use super::{portfolio, BuyEntry, CoinApiRecord, RawJsonData};
+
+const QUOTE_CURRENCY: &str = "USD";
+const CRYPTO_ACCOUNT: &str = "Assets:Crypto";
+const CASH_ACCOUNT: &str = "Assets:Cash:USD";
+const FEE_ACCOUNT: &str = "Expenses:Fees";
+/// The longest commodity name Beancount reads.
+const MAX_BEANCOUNT_COMMODITY_LEN: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerFormat {
+    Beancount,
+    Hledger,
+}
+
+impl LedgerFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            LedgerFormat::Beancount => "beancount",
+            LedgerFormat::Hledger => "journal",
+        }
+    }
+
+    /// Anything that isn't a `.beancount` file is read as an hledger journal.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Self {
+        if extension.eq_ignore_ascii_case("beancount") {
+            LedgerFormat::Beancount
+        } else {
+            LedgerFormat::Hledger
+        }
+    }
+}
+
+/// A buy of `amount` units of `symbol` that cost `cost_usd` in total, and the fee
+/// paid on it, which is a posting of its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalTransaction {
+    pub date: NaiveDate,
+    pub symbol: String,
+    pub amount: f64,
+    pub cost_usd: f64,
+    pub fee: Option<Fee>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceDirective {
+    pub date: NaiveDate,
+    pub symbol: String,
+    pub price_usd: f64,
+}
+
+/// The paid entries as plain-text accounting sees them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Journal {
+    pub commodities: BTreeSet<String>,
+    pub transactions: Vec<JournalTransaction>,
+    pub prices: Vec<PriceDirective>,
+    /// Entries left out because their date couldn't be read or they bought nothing.
+    pub skipped_entries: usize,
+    /// Postings left out of an import, such as buys with a cost in another currency.
+    pub skipped_postings: Vec<LedgerParseError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerParseError {
+    pub line: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for LedgerParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// A symbol Beancount can't take as a commodity, such as `1INCH`. hledger quotes them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCommodity(pub String);
+
+impl fmt::Display for InvalidCommodity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` can't be a Beancount commodity, which starts with a capital letter and \
+             has only capitals, digits and '._-; export as hledger instead",
+            self.0
+        )
+    }
+}
+
+impl Journal {
+    /// Builds the journal of `raw_json_data`, with a price directive dated `today` for
+    /// every coin that has a fetched quote.
+    #[must_use]
+    pub fn from_entries(
+        raw_json_data: &RawJsonData,
+        crypto_names_to_prices: Option<&HashMap<String, Vec<CoinApiRecord>>>,
+        today: NaiveDate,
+    ) -> Self {
+        let mut journal = Journal::default();
+
+        for (symbol, entries) in raw_json_data {
+            journal.commodities.insert(symbol.clone());
+
+            for entry in entries {
+                match entry.date() {
+                    Some(date) if entry.amount > 0.0 => {
+                        // A fee in a coin that isn't held still needs its account
+                        if let Some(fee) = entry.fee.as_ref().filter(|fee| !fee.is_in_fiat()) {
+                            journal.commodities.insert(fee.asset.clone());
+                        }
+
+                        journal.transactions.push(JournalTransaction {
+                            date,
+                            symbol: symbol.clone(),
+                            amount: entry.amount,
+                            cost_usd: entry.amount_usd,
+                            fee: entry.fee.clone(),
+                        });
+                    }
+                    _ => journal.skipped_entries += 1,
+                }
+            }
+
+            if let Some(price_usd) = crypto_names_to_prices
+                .and_then(|prices| portfolio::latest_usd_price(prices, symbol))
+            {
+                journal.prices.push(PriceDirective {
+                    date: today,
+                    symbol: symbol.clone(),
+                    price_usd,
+                });
+            }
+        }
+
+        journal
+            .transactions
+            .sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.symbol.cmp(&b.symbol)));
+        journal.prices.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        journal
+    }
+
+    /// The journal back as paid entries, one entry per transaction.
+    #[must_use]
+    pub fn into_entries(self) -> RawJsonData {
+        let mut raw_json_data = RawJsonData::new();
+
+        for transaction in self.transactions {
+            raw_json_data
+                .entry(transaction.symbol)
+                .or_default()
+                .push(BuyEntry {
+                    is_stable_coin: None,
+                    date: transaction.date.format("%Y-%m-%d").to_string(),
+                    amount_usd: transaction.cost_usd,
+                    amount: transaction.amount,
+                    fee: transaction.fee,
+                });
+        }
+
+        raw_json_data
+    }
+
+    /// Fails for Beancount when a symbol isn't a valid commodity there.
+    pub fn render(&self, format: LedgerFormat) -> Result<String, InvalidCommodity> {
+        let mut output = String::new();
+
+        if format == LedgerFormat::Beancount {
+            if let Some(commodity) = self
+                .commodities
+                .iter()
+                .find(|commodity| !is_beancount_commodity(commodity))
+            {
+                return Err(InvalidCommodity(commodity.clone()));
+            }
+        }
+
+        // Writing into a `String` can't fail
+        _ = match format {
+            LedgerFormat::Beancount => self.write_beancount(&mut output),
+            LedgerFormat::Hledger => self.write_hledger(&mut output),
+        };
+
+        Ok(output)
+    }
+
+    fn write_beancount(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        let opened_on = self
+            .transactions
+            .first()
+            .map(|transaction| transaction.date)
+            .or_else(|| self.prices.first().map(|price| price.date))
+            .unwrap_or_default();
+
+        writeln!(w, "option \"operating_currency\" \"{QUOTE_CURRENCY}\"")?;
+        writeln!(w)?;
+        writeln!(w, "{opened_on} commodity {QUOTE_CURRENCY}")?;
+        for commodity in &self.commodities {
+            writeln!(w, "{opened_on} commodity {commodity}")?;
+        }
+        writeln!(w)?;
+        writeln!(w, "{opened_on} open {CASH_ACCOUNT} {QUOTE_CURRENCY}")?;
+        for commodity in &self.commodities {
+            writeln!(
+                w,
+                "{opened_on} open {CRYPTO_ACCOUNT}:{commodity} {commodity}"
+            )?;
+        }
+
+        for transaction in &self.transactions {
+            let JournalTransaction {
+                date,
+                symbol,
+                amount,
+                cost_usd,
+                fee,
+            } = transaction;
+
+            writeln!(w)?;
+            writeln!(w, "{date} * \"Buy {symbol}\"")?;
+            writeln!(
+                w,
+                "  {CRYPTO_ACCOUNT}:{symbol}  {amount} {symbol} {{{} {QUOTE_CURRENCY}}}",
+                cost_usd / amount
+            )?;
+            if let Some(fee) = fee {
+                write_fee_postings(w, transaction, fee, "  ", &fee.asset, " {}")?;
+            }
+            writeln!(w, "  {CASH_ACCOUNT}")?;
+        }
+
+        if !self.prices.is_empty() {
+            writeln!(w)?;
+        }
+        for PriceDirective {
+            date,
+            symbol,
+            price_usd,
+        } in &self.prices
+        {
+            writeln!(w, "{date} price {symbol} {price_usd} {QUOTE_CURRENCY}")?;
+        }
+
+        Ok(())
+    }
+
+    fn write_hledger(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(w, "commodity {QUOTE_CURRENCY}")?;
+        for commodity in &self.commodities {
+            writeln!(w, "commodity {}", hledger_commodity(commodity))?;
+        }
+
+        for transaction in &self.transactions {
+            let JournalTransaction {
+                date,
+                symbol,
+                amount,
+                cost_usd,
+                fee,
+            } = transaction;
+            let commodity = hledger_commodity(symbol);
+
+            writeln!(w)?;
+            writeln!(w, "{date} * Buy {symbol}")?;
+            writeln!(
+                w,
+                "    {CRYPTO_ACCOUNT}:{symbol}  {amount} {commodity} @@ {cost_usd} {QUOTE_CURRENCY}"
+            )?;
+            if let Some(fee) = fee {
+                write_fee_postings(
+                    w,
+                    transaction,
+                    fee,
+                    "    ",
+                    &hledger_commodity(&fee.asset),
+                    "",
+                )?;
+            }
+            writeln!(w, "    {CASH_ACCOUNT}")?;
+        }
+
+        if !self.prices.is_empty() {
+            writeln!(w)?;
+        }
+        for PriceDirective {
+            date,
+            symbol,
+            price_usd,
+        } in &self.prices
+        {
+            let commodity = hledger_commodity(symbol);
+
+            writeln!(w, "P {date} {commodity} {price_usd} {QUOTE_CURRENCY}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the buys back from a journal: every posting of a non-USD commodity that
+    /// carries a USD cost becomes a transaction, and a posting to the fee account the
+    /// fee of the buy before it. Other directives are skipped, and so are postings that
+    /// can't be read, which are listed in [`Journal::skipped_postings`].
+    #[must_use]
+    pub fn parse(input: &str, format: LedgerFormat) -> Journal {
+        let mut journal = Journal::default();
+        let mut transaction_date = None;
+        // Where the transactions of the current header start
+        let mut first_of_header = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let content = line.split(';').next().unwrap_or_default().trim_end();
+
+            if content.trim().is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                transaction_date = parse_transaction_date(content, format);
+                first_of_header = journal.transactions.len();
+                continue;
+            }
+
+            let Some(date) = transaction_date else {
+                continue;
+            };
+            let Some((account, amount)) = split_posting(content.trim()) else {
+                continue;
+            };
+
+            let parsed = if account == FEE_ACCOUNT {
+                parse_fee(amount).and_then(|fee| {
+                    if !fee.is_in_fiat() {
+                        journal.commodities.insert(fee.asset.clone());
+                    }
+
+                    let transaction = journal.transactions[first_of_header..]
+                        .last_mut()
+                        .ok_or_else(|| "a fee without a buy before it".to_owned())?;
+                    transaction.fee = Some(fee);
+                    Ok(())
+                })
+            } else {
+                parse_posting(amount, date).map(|transaction| {
+                    if let Some(transaction) = transaction {
+                        journal.commodities.insert(transaction.symbol.clone());
+                        journal.transactions.push(transaction);
+                    }
+                })
+            };
+
+            if let Err(reason) = parsed {
+                journal.skipped_postings.push(LedgerParseError {
+                    line: line_number,
+                    reason,
+                });
+            }
+        }
+
+        journal
+    }
+}
+
+/// A capital letter, then capitals, digits or `'._-`, ending in a capital or a digit.
+fn is_beancount_commodity(symbol: &str) -> bool {
+    let (Some(first), Some(last)) = (symbol.bytes().next(), symbol.bytes().last()) else {
+        return false;
+    };
+
+    (2..=MAX_BEANCOUNT_COMMODITY_LEN).contains(&symbol.len())
+        && first.is_ascii_uppercase()
+        && (last.is_ascii_uppercase() || last.is_ascii_digit())
+        && symbol.bytes().all(|byte| {
+            byte.is_ascii_uppercase() || byte.is_ascii_digit() || b"'._-".contains(&byte)
+        })
+}
+
+/// hledger wants commodity symbols containing anything but letters to be quoted.
+fn hledger_commodity(symbol: &str) -> String {
+    if symbol.chars().all(char::is_alphabetic) {
+        symbol.to_owned()
+    } else {
+        format!("\"{symbol}\"")
+    }
+}
+
+/// The date of a transaction header, `None` for any other top-level directive.
+fn parse_transaction_date(header: &str, format: LedgerFormat) -> Option<NaiveDate> {
+    let mut words = header.split_whitespace();
+    let date = words.next()?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y/%m/%d"))
+        .ok()?;
+
+    match (format, words.next()) {
+        (LedgerFormat::Beancount, Some("*" | "!" | "txn")) | (LedgerFormat::Hledger, _) => {
+            Some(date)
+        }
+        _ => None,
+    }
+}
+
+/// Writes the fee's posting and, for a fee paid in crypto, the posting that takes it
+/// out of its holding. A fee in the bought coin is valued at the price of the buy when
+/// it has no value of its own, the cash posting balances the rest.
+fn write_fee_postings(
+    w: &mut impl fmt::Write,
+    transaction: &JournalTransaction,
+    fee: &Fee,
+    indent: &str,
+    commodity: &str,
+    lot: &str,
+) -> fmt::Result {
+    let amount = fee.amount;
+
+    if fee.is_in_fiat() {
+        return writeln!(w, "{indent}{FEE_ACCOUNT}  {amount} {FIAT_ASSET}");
+    }
+
+    let amount_usd = fee.amount_usd.or_else(|| {
+        fee.is_in(&transaction.symbol)
+            .then(|| transaction.cost_usd / transaction.amount * amount)
+    });
+    match amount_usd {
+        Some(amount_usd) => writeln!(
+            w,
+            "{indent}{FEE_ACCOUNT}  {amount} {commodity} @@ {amount_usd} {QUOTE_CURRENCY}"
+        )?,
+        None => writeln!(w, "{indent}{FEE_ACCOUNT}  {amount} {commodity}")?,
+    }
+    writeln!(
+        w,
+        "{indent}{CRYPTO_ACCOUNT}:{}  -{amount} {commodity}{lot}",
+        fee.asset
+    )
+}
+
+/// Splits a posting into its account and its amount, which are separated by two spaces
+/// or a tab since account names may contain single spaces. `None` for a posting whose
+/// amount is left for the journal to infer.
+fn split_posting(posting: &str) -> Option<(&str, &str)> {
+    let separator = [posting.find("  "), posting.find('\t')]
+        .into_iter()
+        .flatten()
+        .min()?;
+    let amount = posting[separator..].trim();
+
+    (!amount.is_empty()).then_some((&posting[..separator], amount))
+}
+
+/// `AMOUNT ASSET`, valued with `@ UNIT USD` or `@@ TOTAL USD` when not in USD.
+fn parse_fee(posting_amount: &str) -> Result<Fee, String> {
+    let mut words = posting_amount.split_whitespace();
+    let (Some(amount), Some(asset)) = (words.next(), words.next()) else {
+        return Err(format!("expected a fee amount, got `{posting_amount}`"));
+    };
+    let amount = parse_number(amount)?;
+    let annotation = words.collect::<Vec<&str>>().join(" ");
+
+    let amount_usd = if let Some(total) = annotation.strip_prefix("@@") {
+        Some(parse_usd(total)?)
+    } else if let Some(unit) = annotation.strip_prefix('@') {
+        Some(parse_usd(unit)? * amount)
+    } else {
+        None
+    };
+
+    Ok(Fee {
+        amount,
+        asset: asset.trim_matches('"').to_owned(),
+        amount_usd,
+    })
+}
+
+/// `AMOUNT SYMBOL {UNIT USD}`, `... @ UNIT USD` or `... @@ TOTAL USD`. Postings that
+/// take coins out, such as the ones paying a fee, aren't buys.
+fn parse_posting(
+    posting_amount: &str,
+    date: NaiveDate,
+) -> Result<Option<JournalTransaction>, String> {
+    let mut words = posting_amount.split_whitespace();
+    let (Some(amount), Some(symbol)) = (words.next(), words.next()) else {
+        return Ok(None);
+    };
+    let symbol = symbol.trim_matches('"');
+
+    if symbol == QUOTE_CURRENCY {
+        return Ok(None);
+    }
+
+    let amount = parse_number(amount)?;
+    if amount <= 0.0 {
+        return Ok(None);
+    }
+
+    let annotation = words.collect::<Vec<&str>>().join(" ");
+    let annotation = annotation.trim();
+
+    // Beancount costs may also carry a lot date or label after a comma
+    let cost_usd = if let Some(total_cost) = annotation.strip_prefix("{{") {
+        parse_usd(total_cost.split(['}', ',']).next().unwrap_or_default())?
+    } else if let Some(unit_cost) = annotation.strip_prefix('{') {
+        parse_usd(unit_cost.split(['}', ',']).next().unwrap_or_default())? * amount
+    } else if let Some(total_cost) = annotation.strip_prefix("@@") {
+        parse_usd(total_cost)?
+    } else if let Some(unit_cost) = annotation.strip_prefix('@') {
+        parse_usd(unit_cost)? * amount
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(JournalTransaction {
+        date,
+        symbol: symbol.to_owned(),
+        amount,
+        cost_usd,
+        fee: None,
+    }))
+}
+
+fn parse_usd(amount: &str) -> Result<f64, String> {
+    let mut words = amount.split_whitespace();
+
+    match (words.next(), words.next()) {
+        (Some(number), Some(QUOTE_CURRENCY)) => parse_number(number),
+        _ => Err(format!(
+            "expected a cost in {QUOTE_CURRENCY}, got `{amount}`"
+        )),
+    }
+}
+
+fn parse_number(number: &str) -> Result<f64, String> {
+    number
+        .replace(',', "")
+        .parse::<f64>()
+        .map_err(|e| format!("`{number}`: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::super::fees::Fee;
+    use super::super::{BuyEntry, RawJsonData};
+    use super::{InvalidCommodity, Journal, LedgerFormat};
+
+    fn buy(fee: Option<Fee>) -> BuyEntry {
+        BuyEntry {
+            is_stable_coin: None,
+            date: "2024-05-20".to_owned(),
+            amount_usd: 300.0,
+            amount: 30.0,
+            fee,
+        }
+    }
+
+    #[test]
+    fn accounts_may_contain_spaces() {
+        let journal = Journal::parse(
+            "2024-03-01 * Buy BTC\n    Assets:Crypto:Cold Wallet  0.01 BTC @@ 600 USD\n\
+             \tExpenses:Fees\t1.5 USD\n    Assets:Cash:USD\n",
+            LedgerFormat::Hledger,
+        );
+
+        assert_eq!(journal.skipped_postings, []);
+        assert_eq!(journal.transactions.len(), 1);
+        assert_eq!(journal.transactions[0].symbol, "BTC");
+        assert!((journal.transactions[0].cost_usd - 600.0).abs() < f64::EPSILON);
+        assert_eq!(
+            journal.transactions[0]
+                .fee
+                .as_ref()
+                .map(|fee| fee.asset.as_str()),
+            Some("USD")
+        );
+    }
+
+    #[test]
+    fn skips_postings_it_cannot_read() {
+        let journal = Journal::parse(
+            "2024-03-01 * \"Buy BTC\"\n  Assets:Crypto:BTC  0.01 BTC {550 EUR}\n\
+             \n2024-03-02 * \"Buy ETH\"\n  Assets:Crypto:ETH  0.5 ETH {1800 USD}\n",
+            LedgerFormat::Beancount,
+        );
+
+        assert_eq!(journal.transactions.len(), 1);
+        assert_eq!(journal.transactions[0].symbol, "ETH");
+        assert_eq!(journal.skipped_postings.len(), 1);
+        assert_eq!(journal.skipped_postings[0].line, 2);
+    }
+
+    #[test]
+    fn opens_accounts_for_fees_in_coins_not_held() {
+        let raw_json_data = RawJsonData::from([(
+            "UNI".to_owned(),
+            vec![buy(Some(Fee {
+                amount: 2.5,
+                asset: "MATIC".to_owned(),
+                amount_usd: Some(1.25),
+            }))],
+        )]);
+        let journal = Journal::from_entries(
+            &raw_json_data,
+            None,
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+        );
+        let beancount = journal.render(LedgerFormat::Beancount).unwrap();
+
+        assert!(beancount.contains("2024-05-20 commodity MATIC\n"));
+        assert!(beancount.contains("2024-05-20 open Assets:Crypto:MATIC MATIC\n"));
+        assert!(beancount.contains("  Expenses:Fees  2.5 MATIC @@ 1.25 USD\n"));
+        assert!(beancount.contains("  Assets:Crypto:MATIC  -2.5 MATIC {}\n"));
+
+        let parsed = Journal::parse(&beancount, LedgerFormat::Beancount);
+        assert_eq!(parsed.skipped_postings, []);
+        assert_eq!(parsed.commodities, journal.commodities);
+        assert_eq!(
+            parsed.transactions[0]
+                .fee
+                .as_ref()
+                .map(|fee| fee.asset.as_str()),
+            Some("MATIC")
+        );
+    }
+
+    #[test]
+    fn beancount_rejects_what_hledger_quotes() {
+        let raw_json_data = RawJsonData::from([("1INCH".to_owned(), vec![buy(None)])]);
+        let journal = Journal::from_entries(
+            &raw_json_data,
+            None,
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+        );
+
+        assert_eq!(
+            journal.render(LedgerFormat::Beancount),
+            Err(InvalidCommodity("1INCH".to_owned()))
+        );
+        assert!(journal
+            .render(LedgerFormat::Hledger)
+            .unwrap()
+            .contains("Assets:Crypto:1INCH  30 \"1INCH\" @@ 300 USD\n"));
+    }
+}
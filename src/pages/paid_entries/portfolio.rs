@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use super::{fees::FeeAllocation, stablecoins, BuyEntry, CoinApiRecord, RawJsonData};
+
+/// What the paid entries add up to for a single coin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoinHolding {
+    pub symbol: String,
+    pub amount: f64,
+    pub cost_usd: f64,
+    /// Every fee paid on the entries that has a value, whether it's in `cost_usd` or not.
+    pub fees_usd: f64,
+    /// Fees paid on top of the trades that are left out of `cost_usd`, with
+    /// [`FeeAllocation::Expense`].
+    pub expensed_fees_usd: f64,
+    /// Fees in a third asset recorded without their value, which count for nothing.
+    pub unvalued_fees: usize,
+    pub is_stable_coin: bool,
+}
+
+impl CoinHolding {
+    /// Fees in the coin itself are taken off the bought amount, which already accounts
+    /// for them. Fees in fiat or a third asset were paid on top, and are allocated to
+    /// cost basis or expenses. Spending the third asset is left to [`holdings`], which
+    /// sees that asset too.
+    #[must_use]
+    pub fn from_entries(symbol: &str, entries: &[BuyEntry], fee_allocation: FeeAllocation) -> Self {
+        entries.iter().fold(
+            Self {
+                symbol: symbol.to_owned(),
+                amount: 0.0,
+                cost_usd: 0.0,
+                fees_usd: 0.0,
+                expensed_fees_usd: 0.0,
+                unvalued_fees: 0,
+                is_stable_coin: stablecoins::is_stable_coin(symbol, entries),
+            },
+            |mut holding, entry| {
+                holding.amount += entry.amount;
+                holding.cost_usd += entry.amount_usd;
+
+                let Some(fee) = &entry.fee else {
+                    return holding;
+                };
+                let Some(fee_usd) = fee.value_usd(symbol, entry) else {
+                    holding.unvalued_fees += 1;
+                    return holding;
+                };
+
+                holding.fees_usd += fee_usd;
+                if fee.is_in(symbol) {
+                    holding.amount -= fee.amount;
+                } else {
+                    match fee_allocation {
+                        FeeAllocation::Capitalize => holding.cost_usd += fee_usd,
+                        FeeAllocation::Expense => holding.expensed_fees_usd += fee_usd,
+                    }
+                }
+
+                holding
+            },
+        )
+    }
+
+    /// Takes `amount` out of the holding along with its share of the cost basis, at
+    /// average cost.
+    pub fn spend(&mut self, amount: f64) {
+        if self.amount > 0.0 {
+            self.cost_usd -= self.cost_usd * (amount / self.amount).min(1.0);
+        }
+        self.amount -= amount;
+    }
+
+    #[must_use]
+    pub fn value_usd(&self, price: f64) -> f64 {
+        self.amount * price
+    }
+
+    /// Fees paid on top come off either way, through the cost basis or as expenses.
+    #[must_use]
+    pub fn pnl_usd(&self, price: f64) -> f64 {
+        self.value_usd(price) - self.cost_usd - self.expensed_fees_usd
+    }
+}
+
+/// Every coin of the paid entries, sorted by symbol. Fees paid in a third asset are
+/// spent from that asset's holding, if the entries hold any of it.
+#[must_use]
+pub fn holdings(raw_json_data: &RawJsonData, fee_allocation: FeeAllocation) -> Vec<CoinHolding> {
+    let mut holdings: Vec<CoinHolding> = raw_json_data
+        .iter()
+        .map(|(symbol, entries)| CoinHolding::from_entries(symbol, entries, fee_allocation))
+        .collect();
+
+    for (symbol, entries) in raw_json_data {
+        for fee in entries.iter().filter_map(|entry| entry.fee.as_ref()) {
+            if !fee.is_in_third_asset(symbol) {
+                continue;
+            }
+
+            if let Some(holding) = holdings
+                .iter_mut()
+                .find(|holding| fee.is_in(&holding.symbol))
+            {
+                holding.spend(fee.amount);
+            }
+        }
+    }
+
+    holdings.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    holdings
+}
+
+/// CMC may return several coins for one symbol, the first one is the ranked one.
+#[must_use]
+pub fn latest_usd_price(
+    crypto_names_to_prices: &HashMap<String, Vec<CoinApiRecord>>,
+    symbol: &str,
+) -> Option<f64> {
+    crypto_names_to_prices
+        .get(symbol)
+        .and_then(|records| records.first())
+        .and_then(|record| record.quote.usd.price)
+}
+
+/// Totals over the holdings that have a price.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortfolioSummary {
+    pub cost_usd: f64,
+    pub value_usd: f64,
+    /// Value of the stablecoins, whether they count towards P&L or not.
+    pub cash_like_usd: f64,
+    /// Every fee with a value, whether its holding counts towards P&L or not.
+    pub fees_usd: f64,
+    /// Fees that aren't in `cost_usd`, which P&L still has to account for. Only those of
+    /// the holdings that are in `cost_usd`.
+    pub expensed_fees_usd: f64,
+    pub unvalued_fees: usize,
+}
+
+impl PortfolioSummary {
+    /// With `exclude_stable_coins`, stablecoins only add to `cash_like_usd`. `fees_usd`
+    /// and `unvalued_fees` count every holding, priced or not.
+    #[must_use]
+    pub fn new(
+        holdings: &[CoinHolding],
+        crypto_names_to_prices: &HashMap<String, Vec<CoinApiRecord>>,
+        exclude_stable_coins: bool,
+    ) -> Self {
+        let mut summary = Self::default();
+
+        for holding in holdings {
+            summary.fees_usd += holding.fees_usd;
+            summary.unvalued_fees += holding.unvalued_fees;
+
+            let Some(price) = latest_usd_price(crypto_names_to_prices, &holding.symbol) else {
+                continue;
+            };
+
+            if holding.is_stable_coin {
+                summary.cash_like_usd += holding.value_usd(price);
+
+                if exclude_stable_coins {
+                    continue;
+                }
+            }
+
+            summary.cost_usd += holding.cost_usd;
+            summary.expensed_fees_usd += holding.expensed_fees_usd;
+            summary.value_usd += holding.value_usd(price);
+        }
+
+        summary
+    }
+
+    #[must_use]
+    pub fn pnl_usd(&self) -> f64 {
+        self.value_usd - self.cost_usd - self.expensed_fees_usd
+    }
+}
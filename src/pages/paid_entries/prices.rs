@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use super::{CoinApiRecord, CoinApiResponse, Quote};
+
+/// Daily USD closes of a single coin.
+pub type PriceHistory = BTreeMap<NaiveDate, f64>;
+
+/// Daily USD closes, keyed by coin symbol.
+pub type HistoricalPrices = HashMap<String, PriceHistory>;
+
+#[derive(Debug, Deserialize)]
+struct HistoricalApiResponse {
+    data: HashMap<String, Vec<HistoricalApiRecord>>,
+}
+
+impl HistoricalApiResponse {
+    /// The first, ranked, coin of each symbol.
+    fn into_history(self) -> HistoricalPrices {
+        self.data
+            .into_iter()
+            .filter_map(|(symbol, records)| {
+                let record = records.into_iter().next()?;
+                let history = record
+                    .quotes
+                    .into_iter()
+                    .filter_map(|it| Some((it.timestamp.date_naive(), it.quote.usd.price?)))
+                    .collect();
+
+                Some((symbol, history))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoricalApiRecord {
+    quotes: Vec<HistoricalApiQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoricalApiQuote {
+    timestamp: DateTime<Utc>,
+    quote: Quote,
+}
+
+/// Talks to the `CoinMarketCap` pro API, the only price source for now.
+pub struct CoinMarketCapProvider {
+    api_key: String,
+    base_url: String,
+}
+
+impl CoinMarketCapProvider {
+    pub const BASE_URL: &str = "https://pro-api.coinmarketcap.com";
+
+    #[must_use]
+    pub fn new(api_key: String) -> Self {
+        Self::with_base_url(api_key, Self::BASE_URL.to_owned())
+    }
+
+    /// Against another host serving the same API, such as a sandbox or a mock.
+    #[must_use]
+    pub fn with_base_url(api_key: String, base_url: String) -> Self {
+        Self { api_key, base_url }
+    }
+
+    fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        reqwest::Client::new()
+            .get(format!("{}{path}", self.base_url))
+            .header("X-CMC_PRO_API_KEY", &self.api_key)
+    }
+
+    pub async fn latest_quotes(
+        &self,
+        symbols: &[String],
+    ) -> Result<HashMap<String, Vec<CoinApiRecord>>, reqwest::Error> {
+        let response = self
+            .get("/v2/cryptocurrency/quotes/latest")
+            .query(&[("symbol", symbols.join(","))])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CoinApiResponse>()
+            .await?;
+
+        Ok(response.data)
+    }
+
+    /// Daily closes between `from` and `to`, both included.
+    pub async fn daily_history(
+        &self,
+        symbols: &[String],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HistoricalPrices, reqwest::Error> {
+        let response = self
+            .get("/v2/cryptocurrency/quotes/historical")
+            .query(&[
+                ("symbol", symbols.join(",")),
+                ("time_start", from.to_string()),
+                ("time_end", to.to_string()),
+                ("interval", "daily".to_owned()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<HistoricalApiResponse>()
+            .await?;
+
+        Ok(response.into_history())
+    }
+}
+
+/// A saved `/v2/cryptocurrency/quotes/latest` response, as
+/// [`CoinMarketCapProvider::latest_quotes`] reads it.
+pub fn parse_latest_quotes(
+    json: &[u8],
+) -> Result<HashMap<String, Vec<CoinApiRecord>>, serde_json::Error> {
+    Ok(serde_json::from_slice::<CoinApiResponse>(json)?.data)
+}
+
+/// A saved `/v2/cryptocurrency/quotes/historical` response, as
+/// [`CoinMarketCapProvider::daily_history`] reads it.
+pub fn parse_daily_history(json: &[u8]) -> Result<HistoricalPrices, serde_json::Error> {
+    Ok(serde_json::from_slice::<HistoricalApiResponse>(json)?.into_history())
+}
+
+/// The last known close on or before `date`, or the first one after it when the
+/// history starts later.
+#[must_use]
+pub fn price_on(history: &PriceHistory, date: NaiveDate) -> Option<f64> {
+    history
+        .range(..=date)
+        .next_back()
+        .or_else(|| history.range(date..).next())
+        .map(|(_, price)| *price)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::{price_on, CoinMarketCapProvider, PriceHistory};
+
+    /// Answers a single HTTP request with `body`, and hands back the whole request.
+    async fn responder(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0_u8; 4096];
+            let read = stream.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..read]).into_owned();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+                 connection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    fn provider(url: String) -> CoinMarketCapProvider {
+        CoinMarketCapProvider::with_base_url("KEY".to_owned(), url)
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fetches_latest_quotes() {
+        let (url, handle) = responder(
+            r#"{"data":{"BTC":[{"id":1,"name":"Bitcoin","symbol":"BTC","platform":null,
+                "quote":{"USD":{"price":97250.0}}}]}}"#,
+        )
+        .await;
+
+        let quotes = provider(url)
+            .latest_quotes(&["BTC".to_owned(), "ETH".to_owned()])
+            .await
+            .unwrap();
+        let request = handle.await.unwrap();
+
+        assert!(request.starts_with("GET /v2/cryptocurrency/quotes/latest?symbol=BTC%2CETH "));
+        assert!(request.to_lowercase().contains("x-cmc_pro_api_key: key"));
+        assert_eq!(quotes["BTC"][0].quote.usd.price, Some(97250.0));
+    }
+
+    #[tokio::test]
+    async fn fetches_daily_history_of_the_ranked_coin() {
+        let (url, handle) = responder(
+            r#"{"data":{"BTC":[
+                {"quotes":[
+                    {"timestamp":"2025-01-01T23:59:59.999Z","quote":{"USD":{"price":94000.0}}},
+                    {"timestamp":"2025-01-02T23:59:59.999Z","quote":{"USD":{"price":null}}},
+                    {"timestamp":"2025-01-03T23:59:59.999Z","quote":{"USD":{"price":96000.0}}}
+                ]},
+                {"quotes":[
+                    {"timestamp":"2025-01-01T23:59:59.999Z","quote":{"USD":{"price":0.01}}}
+                ]}
+            ]}}"#,
+        )
+        .await;
+
+        let history = provider(url)
+            .daily_history(&["BTC".to_owned()], date(1), date(3))
+            .await
+            .unwrap();
+        let request = handle.await.unwrap();
+
+        assert!(request.starts_with(
+            "GET /v2/cryptocurrency/quotes/historical?symbol=BTC\
+             &time_start=2025-01-01&time_end=2025-01-03&interval=daily "
+        ));
+        assert_eq!(
+            history["BTC"],
+            PriceHistory::from([(date(1), 94000.0), (date(3), 96000.0)])
+        );
+    }
+
+    #[test]
+    fn prices_on_the_last_close_or_the_first_one() {
+        let history = PriceHistory::from([(date(2), 10.0), (date(4), 20.0)]);
+
+        assert_eq!(price_on(&history, date(1)), Some(10.0));
+        assert_eq!(price_on(&history, date(3)), Some(10.0));
+        assert_eq!(price_on(&history, date(5)), Some(20.0));
+        assert_eq!(price_on(&PriceHistory::new(), date(1)), None);
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+use printpdf::{BuiltinFont, Mm, PdfDocument};
+use serde::Serialize;
+
+use super::portfolio::{self, CoinHolding, PortfolioSummary};
+use super::CoinApiRecord;
+use crate::fl;
+
+const PDF_PAGE_WIDTH: Mm = Mm(210.0);
+const PDF_PAGE_HEIGHT: Mm = Mm(297.0);
+const PDF_MARGIN: f32 = 15.0;
+const PDF_FONT_SIZE: f32 = 9.0;
+const PDF_LINE_HEIGHT: f32 = 5.0;
+/// Stands in for characters that the builtin fonts' encoding can't show, which would
+/// otherwise be dropped without a trace.
+const PDF_PLACEHOLDER: char = '?';
+/// CSV sums are rounded like the PDF shows them, cents for USD. Prices are quoted, not
+/// summed, so they're written as they came.
+const CSV_USD_DECIMALS: i32 = 2;
+const CSV_AMOUNT_DECIMALS: i32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Pdf,
+}
+
+impl ReportFormat {
+    pub const ALL: [ReportFormat; 3] = [ReportFormat::Csv, ReportFormat::Json, ReportFormat::Pdf];
+
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Pdf => "pdf",
+        }
+    }
+
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        ReportFormat::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+#[derive(Debug)]
+pub enum ReportError {
+    Csv(csv::Error),
+    Json(serde_json::Error),
+    Pdf(printpdf::Error),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::Csv(e) => write!(f, "{e}"),
+            ReportError::Json(e) => write!(f, "{e}"),
+            ReportError::Pdf(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A holding as of the report date. Prices, value and P&L are `None` for coins
+/// without a quote, and P&L for stablecoins left out of it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportRow {
+    pub symbol: String,
+    pub amount: f64,
+    pub price_usd: Option<f64>,
+    pub cost_usd: f64,
+    pub value_usd: Option<f64>,
+    pub pnl_usd: Option<f64>,
+    pub fees_usd: f64,
+    pub is_stable_coin: bool,
+}
+
+impl ReportRow {
+    /// Without the float noise of the sums, for people to read.
+    fn rounded(&self) -> Self {
+        let usd = |usd: f64| round(usd, CSV_USD_DECIMALS);
+
+        Self {
+            symbol: self.symbol.clone(),
+            amount: round(self.amount, CSV_AMOUNT_DECIMALS),
+            price_usd: self.price_usd,
+            cost_usd: usd(self.cost_usd),
+            value_usd: self.value_usd.map(usd),
+            pnl_usd: self.pnl_usd.map(usd),
+            fees_usd: usd(self.fees_usd),
+            is_stable_coin: self.is_stable_coin,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    pub cost_usd: f64,
+    pub value_usd: f64,
+    pub pnl_usd: f64,
+    pub cash_like_usd: f64,
+    pub fees_usd: f64,
+}
+
+/// A snapshot of the holdings, for sending around rather than reading back in.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub generated_on: NaiveDate,
+    pub holdings: Vec<ReportRow>,
+    pub summary: ReportSummary,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(
+        holdings: &[CoinHolding],
+        crypto_names_to_prices: &HashMap<String, Vec<CoinApiRecord>>,
+        exclude_stable_coins: bool,
+        generated_on: NaiveDate,
+    ) -> Self {
+        let summary = PortfolioSummary::new(holdings, crypto_names_to_prices, exclude_stable_coins);
+
+        Self {
+            generated_on,
+            holdings: holdings
+                .iter()
+                .map(|holding| {
+                    let price_usd =
+                        portfolio::latest_usd_price(crypto_names_to_prices, &holding.symbol);
+
+                    ReportRow {
+                        symbol: holding.symbol.clone(),
+                        amount: holding.amount,
+                        price_usd,
+                        cost_usd: holding.cost_usd,
+                        value_usd: price_usd.map(|price| holding.value_usd(price)),
+                        pnl_usd: price_usd
+                            .filter(|_| !(exclude_stable_coins && holding.is_stable_coin))
+                            .map(|price| holding.pnl_usd(price)),
+                        fees_usd: holding.fees_usd,
+                        is_stable_coin: holding.is_stable_coin,
+                    }
+                })
+                .collect(),
+            summary: ReportSummary {
+                cost_usd: summary.cost_usd,
+                value_usd: summary.value_usd,
+                pnl_usd: summary.pnl_usd(),
+                cash_like_usd: summary.cash_like_usd,
+                fees_usd: summary.fees_usd,
+            },
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> Result<Vec<u8>, ReportError> {
+        match format {
+            ReportFormat::Csv => self.render_csv(),
+            ReportFormat::Json => serde_json::to_vec_pretty(self).map_err(ReportError::Json),
+            ReportFormat::Pdf => self.render_pdf(),
+        }
+    }
+
+    /// One row per holding, the summary is left to the other formats.
+    fn render_csv(&self) -> Result<Vec<u8>, ReportError> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        for row in &self.holdings {
+            writer.serialize(row.rounded()).map_err(ReportError::Csv)?;
+        }
+
+        writer
+            .into_inner()
+            .map_err(|e| ReportError::Csv(e.into_error().into()))
+    }
+
+    /// A plain monospaced table, paginated on A4.
+    fn render_pdf(&self) -> Result<Vec<u8>, ReportError> {
+        let title = fl!("report-title", date = self.generated_on.to_string());
+        let (document, page, layer) =
+            PdfDocument::new(&title, PDF_PAGE_WIDTH, PDF_PAGE_HEIGHT, "Report");
+        let font = document
+            .add_builtin_font(BuiltinFont::Courier)
+            .map_err(ReportError::Pdf)?;
+        let bold_font = document
+            .add_builtin_font(BuiltinFont::CourierBold)
+            .map_err(ReportError::Pdf)?;
+
+        let mut layer = document.get_page(page).get_layer(layer);
+        let mut y = PDF_PAGE_HEIGHT.0 - PDF_MARGIN;
+
+        for (line, is_bold) in self.pdf_lines() {
+            if y < PDF_MARGIN {
+                let (page, new_layer) =
+                    document.add_page(PDF_PAGE_WIDTH, PDF_PAGE_HEIGHT, "Report");
+                layer = document.get_page(page).get_layer(new_layer);
+                y = PDF_PAGE_HEIGHT.0 - PDF_MARGIN;
+            }
+
+            layer.use_text(
+                to_win_ansi(&line),
+                PDF_FONT_SIZE,
+                Mm(PDF_MARGIN),
+                Mm(y),
+                if is_bold { &bold_font } else { &font },
+            );
+            y -= PDF_LINE_HEIGHT;
+        }
+
+        document.save_to_bytes().map_err(ReportError::Pdf)
+    }
+
+    /// The lines of the PDF, with whether they're headings.
+    fn pdf_lines(&self) -> Vec<(String, bool)> {
+        let format_usd =
+            |usd: Option<f64>| usd.map_or_else(|| "-".to_owned(), |usd| format!("{usd:.2}"));
+        let ReportSummary {
+            cost_usd,
+            value_usd,
+            pnl_usd,
+            cash_like_usd,
+            fees_usd,
+        } = self.summary;
+
+        let summary_line =
+            |label: String, usd: String| (format!("{label:<14}{usd:>14} USD"), false);
+
+        let mut lines = vec![
+            (
+                fl!("report-title", date = self.generated_on.to_string()),
+                true,
+            ),
+            (String::new(), false),
+            summary_line(fl!("report-cost"), format!("{cost_usd:.2}")),
+            summary_line(fl!("report-value"), format!("{value_usd:.2}")),
+            summary_line(fl!("report-pnl"), format!("{pnl_usd:+.2}")),
+            summary_line(fl!("report-cash-like"), format!("{cash_like_usd:.2}")),
+            summary_line(fl!("report-fees"), format!("{fees_usd:.2}")),
+            (String::new(), false),
+            (
+                format!(
+                    "{:<8} {:>16} {:>12} {:>12} {:>12} {:>12}",
+                    fl!("report-symbol"),
+                    fl!("report-amount"),
+                    fl!("report-price"),
+                    fl!("report-cost"),
+                    fl!("report-value"),
+                    fl!("report-pnl")
+                ),
+                true,
+            ),
+        ];
+
+        lines.extend(self.holdings.iter().map(|row| {
+            (
+                format!(
+                    "{:<8} {:>16.8} {:>12} {:>12.2} {:>12} {:>12}",
+                    row.symbol,
+                    row.amount,
+                    format_usd(row.price_usd),
+                    row.cost_usd,
+                    format_usd(row.value_usd),
+                    row.pnl_usd
+                        .map_or_else(|| "-".to_owned(), |pnl| format!("{pnl:+.2}")),
+                ),
+                false,
+            )
+        }));
+
+        lines
+    }
+}
+
+fn round(value: f64, decimals: i32) -> f64 {
+    let scale = 10_f64.powi(decimals);
+
+    (value * scale).round() / scale
+}
+
+/// `text` with every character that the builtin fonts' Windows-1252 encoding lacks
+/// replaced by [`PDF_PLACEHOLDER`].
+fn to_win_ansi(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let encoded = printpdf::lopdf::Document::encode_text(
+                Some("WinAnsiEncoding"),
+                c.encode_utf8(&mut [0; 4]),
+            );
+
+            if encoded.is_empty() {
+                PDF_PLACEHOLDER
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::super::demo;
+    use super::super::fees::FeeAllocation;
+    use super::super::portfolio;
+    use super::{to_win_ansi, Report, ReportFormat};
+
+    fn demo_report() -> Report {
+        Report::new(
+            &portfolio::holdings(&demo::paid_entries().unwrap(), FeeAllocation::Capitalize),
+            &demo::latest_quotes().unwrap(),
+            true,
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+        )
+    }
+
+    fn render(format: ReportFormat) -> String {
+        String::from_utf8(demo_report().render(format).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            render(ReportFormat::Csv),
+            include_str!("../../../resources/fixtures/report/demo.csv")
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        assert_eq!(
+            render(ReportFormat::Json),
+            include_str!("../../../resources/fixtures/report/demo.json").trim_end()
+        );
+    }
+
+    #[test]
+    fn replaces_what_the_pdf_fonts_cannot_show() {
+        assert_eq!(to_win_ansi("Café 5€"), "Café 5€");
+        assert_eq!(to_win_ansi("比特币 ₿"), "??? ?");
+    }
+}
@@ -0,0 +1,48 @@
+use super::BuyEntry;
+
+/// How far a stablecoin may drift from its peg before it's flagged, in basis points.
+pub const DEFAULT_DEPEG_BAND_BPS: u32 = 100;
+
+/// The peg every stablecoin is checked against, quotes being in USD.
+const USD_PEG: f64 = 1.0;
+
+/// USD-pegged coins recognized when the entries don't say otherwise.
+const KNOWN_STABLECOINS: [&str; 14] = [
+    "USDT", "USDC", "DAI", "BUSD", "TUSD", "USDP", "FDUSD", "PYUSD", "USDE", "USDS", "FRAX",
+    "GUSD", "LUSD", "USDD",
+];
+
+#[must_use]
+pub fn is_known_stable_coin(symbol: &str) -> bool {
+    KNOWN_STABLECOINS
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(symbol))
+}
+
+/// An explicit `isStableCoin` on any entry wins over the built-in list.
+#[must_use]
+pub fn is_stable_coin(symbol: &str, entries: &[BuyEntry]) -> bool {
+    entries
+        .iter()
+        .find_map(|entry| entry.is_stable_coin)
+        .unwrap_or_else(|| is_known_stable_coin(symbol))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Depeg {
+    pub symbol: String,
+    pub price: f64,
+    pub deviation_bps: f64,
+}
+
+/// `Some` when `price` is further than `band_bps` from the peg.
+#[must_use]
+pub fn depeg(symbol: &str, price: f64, band_bps: u32) -> Option<Depeg> {
+    let deviation_bps = (price - USD_PEG) / USD_PEG * 10_000.0;
+
+    (deviation_bps.abs() > f64::from(band_bps)).then(|| Depeg {
+        symbol: symbol.to_owned(),
+        price,
+        deviation_bps,
+    })
+}
@@ -0,0 +1,436 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::portfolio::CoinHolding;
+use crate::config::UniConfig;
+
+/// Balances within this share of the recorded holdings are considered reconciled.
+const RECONCILIATION_TOLERANCE: f64 = 0.001;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WalletChain {
+    #[default]
+    Bitcoin,
+    Ethereum,
+    Polygon,
+    BnbSmartChain,
+    Arbitrum,
+    Optimism,
+    Base,
+}
+
+impl WalletChain {
+    pub const ALL: [WalletChain; 7] = [
+        WalletChain::Bitcoin,
+        WalletChain::Ethereum,
+        WalletChain::Polygon,
+        WalletChain::BnbSmartChain,
+        WalletChain::Arbitrum,
+        WalletChain::Optimism,
+        WalletChain::Base,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            WalletChain::Bitcoin => "Bitcoin",
+            WalletChain::Ethereum => "Ethereum",
+            WalletChain::Polygon => "Polygon",
+            WalletChain::BnbSmartChain => "BNB Smart Chain",
+            WalletChain::Arbitrum => "Arbitrum One",
+            WalletChain::Optimism => "OP Mainnet",
+            WalletChain::Base => "Base",
+        }
+    }
+
+    /// The symbol of the chain's native coin, as in the paid entries.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            WalletChain::Bitcoin => "BTC",
+            WalletChain::Polygon => "POL",
+            WalletChain::BnbSmartChain => "BNB",
+            WalletChain::Ethereum
+            | WalletChain::Arbitrum
+            | WalletChain::Optimism
+            | WalletChain::Base => "ETH",
+        }
+    }
+
+    /// `None` for chains that aren't served by the EVM explorer.
+    #[must_use]
+    pub fn evm_chain_id(self) -> Option<u64> {
+        match self {
+            WalletChain::Bitcoin => None,
+            WalletChain::Ethereum => Some(1),
+            WalletChain::Polygon => Some(137),
+            WalletChain::BnbSmartChain => Some(56),
+            WalletChain::Arbitrum => Some(42161),
+            WalletChain::Optimism => Some(10),
+            WalletChain::Base => Some(8453),
+        }
+    }
+}
+
+/// A public address whose native coin balance is tracked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WalletAddress {
+    pub chain: WalletChain,
+    pub address: String,
+    pub label: String,
+}
+
+impl WalletAddress {
+    /// A shape check only, the explorer has the final say.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        if self.chain.evm_chain_id().is_some() {
+            self.address
+                .strip_prefix("0x")
+                .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        } else {
+            (26..=90).contains(&self.address.len())
+                && self.address.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WalletBalanceError {
+    Request(reqwest::Error),
+    Explorer(String),
+    InvalidBalance(String),
+}
+
+impl fmt::Display for WalletBalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalletBalanceError::Request(e) => write!(f, "{e}"),
+            WalletBalanceError::Explorer(message) => write!(f, "explorer error: {message}"),
+            WalletBalanceError::InvalidBalance(balance) => {
+                write!(f, "invalid balance `{balance}`")
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for WalletBalanceError {
+    fn from(e: reqwest::Error) -> Self {
+        WalletBalanceError::Request(e)
+    }
+}
+
+/// Esplora's address stats, in satoshis.
+#[derive(Debug, Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStats {
+    funded_txo_sum: u64,
+    spent_txo_sum: u64,
+}
+
+/// Etherscan-style responses carry errors in-band.
+#[derive(Debug, Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    message: String,
+    result: String,
+}
+
+/// Esplora for Bitcoin, the Etherscan multichain API for EVM chains. Both base URLs
+/// can be overridden in `UniConfig`, e.g. to point at a local mock server.
+#[derive(Debug, Clone)]
+pub struct BlockExplorer {
+    bitcoin_url: String,
+    evm_url: String,
+    evm_api_key: Option<String>,
+}
+
+impl BlockExplorer {
+    pub const BITCOIN_URL: &str = "https://blockstream.info/api";
+    pub const EVM_URL: &str = "https://api.etherscan.io/v2/api";
+
+    #[must_use]
+    pub fn from_config(config: &UniConfig) -> Self {
+        Self {
+            bitcoin_url: config
+                .bitcoin_explorer_url
+                .clone()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| Self::BITCOIN_URL.to_owned()),
+            evm_url: config
+                .evm_explorer_url
+                .clone()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| Self::EVM_URL.to_owned()),
+            evm_api_key: config.evm_explorer_api_key.clone(),
+        }
+    }
+
+    /// The native coin balance of `wallet`, in whole coins.
+    #[allow(clippy::cast_precision_loss)]
+    pub async fn balance(&self, wallet: &WalletAddress) -> Result<f64, WalletBalanceError> {
+        let client = reqwest::Client::new();
+
+        match wallet.chain.evm_chain_id() {
+            None => {
+                let address = client
+                    .get(format!(
+                        "{}/address/{}",
+                        self.bitcoin_url.trim_end_matches('/'),
+                        wallet.address
+                    ))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<EsploraAddress>()
+                    .await?;
+
+                let satoshis = address
+                    .chain_stats
+                    .funded_txo_sum
+                    .saturating_sub(address.chain_stats.spent_txo_sum);
+
+                Ok(satoshis as f64 / 100_000_000.0)
+            }
+            Some(chain_id) => {
+                let mut query = vec![
+                    ("chainid", chain_id.to_string()),
+                    ("module", "account".to_owned()),
+                    ("action", "balance".to_owned()),
+                    ("address", wallet.address.clone()),
+                    ("tag", "latest".to_owned()),
+                ];
+                if let Some(api_key) = &self.evm_api_key {
+                    query.push(("apikey", api_key.clone()));
+                }
+
+                let response = client
+                    .get(&self.evm_url)
+                    .query(&query)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<EtherscanResponse>()
+                    .await?;
+
+                if response.status != "1" {
+                    return Err(WalletBalanceError::Explorer(format!(
+                        "{}: {}",
+                        response.message, response.result
+                    )));
+                }
+
+                let wei = response
+                    .result
+                    .parse::<u128>()
+                    .map_err(|_| WalletBalanceError::InvalidBalance(response.result.clone()))?;
+
+                Ok(wei as f64 / 1e18)
+            }
+        }
+    }
+}
+
+/// Recorded holdings of a coin next to what its wallets hold on-chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciliation {
+    pub symbol: String,
+    pub recorded: f64,
+    pub on_chain: f64,
+}
+
+impl Reconciliation {
+    #[must_use]
+    pub fn is_discrepancy(&self) -> bool {
+        (self.on_chain - self.recorded).abs()
+            > self.recorded.abs().max(self.on_chain.abs()) * RECONCILIATION_TOLERANCE
+    }
+}
+
+/// One line per coin that has at least one fetched wallet balance.
+#[must_use]
+pub fn reconcile(
+    holdings: &[CoinHolding],
+    balances: &[(WalletAddress, Result<f64, String>)],
+) -> Vec<Reconciliation> {
+    let mut reconciliations: Vec<Reconciliation> = vec![];
+
+    for (wallet, balance) in balances {
+        let Ok(balance) = balance else {
+            continue;
+        };
+        let symbol = wallet.chain.symbol();
+
+        if let Some(reconciliation) = reconciliations.iter_mut().find(|it| it.symbol == symbol) {
+            reconciliation.on_chain += balance;
+        } else {
+            reconciliations.push(Reconciliation {
+                symbol: symbol.to_owned(),
+                recorded: holdings
+                    .iter()
+                    .find(|holding| holding.symbol == symbol)
+                    .map_or(0.0, |holding| holding.amount),
+                on_chain: *balance,
+            });
+        }
+    }
+
+    reconciliations.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    reconciliations
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::super::portfolio::CoinHolding;
+    use super::{reconcile, BlockExplorer, WalletAddress, WalletBalanceError, WalletChain};
+    use crate::config::UniConfig;
+
+    const BTC_ADDRESS: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+    const EVM_ADDRESS: &str = "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe";
+
+    /// Answers a single HTTP request with `body`, and hands back its request line.
+    async fn responder(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0_u8; 4096];
+            let read = stream.read(&mut request).await.unwrap();
+            let request = String::from_utf8_lossy(&request[..read]).into_owned();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+                 connection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+
+            request.lines().next().unwrap_or_default().to_owned()
+        });
+
+        (url, handle)
+    }
+
+    fn explorer(url: &str) -> BlockExplorer {
+        BlockExplorer::from_config(&UniConfig {
+            bitcoin_explorer_url: Some(url.to_owned()),
+            evm_explorer_url: Some(url.to_owned()),
+            evm_explorer_api_key: Some("KEY".to_owned()),
+            ..UniConfig::default()
+        })
+    }
+
+    fn wallet(chain: WalletChain, address: &str) -> WalletAddress {
+        WalletAddress {
+            chain,
+            address: address.to_owned(),
+            label: String::new(),
+        }
+    }
+
+    fn holding(symbol: &str, amount: f64) -> CoinHolding {
+        CoinHolding {
+            symbol: symbol.to_owned(),
+            amount,
+            cost_usd: 0.0,
+            fees_usd: 0.0,
+            expensed_fees_usd: 0.0,
+            unvalued_fees: 0,
+            is_stable_coin: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_bitcoin_balances_from_esplora() {
+        let (url, handle) =
+            responder(r#"{"chain_stats":{"funded_txo_sum":150000000,"spent_txo_sum":25000000}}"#)
+                .await;
+
+        let balance = explorer(&url)
+            .balance(&wallet(WalletChain::Bitcoin, BTC_ADDRESS))
+            .await
+            .unwrap();
+
+        assert!((balance - 1.25).abs() < f64::EPSILON);
+        assert_eq!(
+            handle.await.unwrap(),
+            format!("GET /address/{BTC_ADDRESS} HTTP/1.1")
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_evm_balances_from_etherscan() {
+        let (url, handle) =
+            responder(r#"{"status":"1","message":"OK","result":"2500000000000000000"}"#).await;
+
+        let balance = explorer(&url)
+            .balance(&wallet(WalletChain::Base, EVM_ADDRESS))
+            .await
+            .unwrap();
+        let request_line = handle.await.unwrap();
+
+        assert!((balance - 2.5).abs() < f64::EPSILON);
+        assert!(request_line.contains("chainid=8453"));
+        assert!(request_line.contains(&format!("address={EVM_ADDRESS}")));
+        assert!(request_line.contains("apikey=KEY"));
+    }
+
+    #[tokio::test]
+    async fn reports_explorer_errors() {
+        let (url, handle) =
+            responder(r#"{"status":"0","message":"NOTOK","result":"Invalid API Key"}"#).await;
+
+        let error = explorer(&url)
+            .balance(&wallet(WalletChain::Ethereum, EVM_ADDRESS))
+            .await
+            .unwrap_err();
+        handle.await.unwrap();
+
+        assert!(
+            matches!(&error, WalletBalanceError::Explorer(message) if message.contains("Invalid"))
+        );
+    }
+
+    #[tokio::test]
+    async fn reconciles_fetched_balances_with_holdings() {
+        let (url, handle) =
+            responder(r#"{"chain_stats":{"funded_txo_sum":50000000,"spent_txo_sum":0}}"#).await;
+        let btc_wallet = wallet(WalletChain::Bitcoin, BTC_ADDRESS);
+        let btc_balance = explorer(&url)
+            .balance(&btc_wallet)
+            .await
+            .map_err(|e| e.to_string());
+        handle.await.unwrap();
+
+        let reconciliations = reconcile(
+            &[holding("BTC", 0.5), holding("ETH", 1.0)],
+            &[
+                (btc_wallet, btc_balance),
+                (wallet(WalletChain::Ethereum, EVM_ADDRESS), Ok(0.6)),
+                (wallet(WalletChain::Arbitrum, EVM_ADDRESS), Ok(0.3)),
+                (
+                    wallet(WalletChain::Polygon, EVM_ADDRESS),
+                    Err("timed out".to_owned()),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            reconciliations
+                .iter()
+                .map(|it| (it.symbol.as_str(), it.is_discrepancy()))
+                .collect::<Vec<_>>(),
+            [("BTC", false), ("ETH", true)]
+        );
+        assert!((reconciliations[1].on_chain - 0.9).abs() < 1e-9);
+    }
+}